@@ -1,4 +1,4 @@
-use crate::{config::Config, DynErrResult};
+use crate::{config::Config, report::Format, DynErrResult};
 use std::{env, io};
 
 pub mod log;
@@ -6,7 +6,8 @@ pub mod rundaemon;
 
 pub enum Command {
     Config,
-    Run,
+    Run(RunOptions),
+    Resend(String),
     RunD,
     InstallD,
     Invalid,
@@ -18,7 +19,14 @@ impl Command {
         let mut args = env::args().skip(1);
         match args.next() {
             Some(command) => match &command[..] {
-                "run" => Command::Run,
+                "run" => match RunOptions::parse(args) {
+                    Some(options) => Command::Run(options),
+                    None => Command::Invalid,
+                },
+                "resend" => match args.next() {
+                    Some(id) => Command::Resend(id),
+                    None => Command::Invalid,
+                },
                 "rund" => Command::RunD,
                 "config" => Command::Config,
                 "installd" => Command::InstallD,
@@ -32,7 +40,8 @@ impl Command {
     pub fn help(&self) -> String {
         match self {
             Command::Config => "allows to configure the slack hook, and repo path".into(),
-            Command::Run => "runs the program".into(),
+            Command::Run(_) => "runs the program".into(),
+            Command::Resend(_) => "re-sends a previous report from history, by its id".into(),
             Command::RunD => "it's designed to be used by the launch agent (daemon)".into(),
             Command::InstallD => "installs the launch agent parameters in user's space".into(),
             Command::Help => format!(
@@ -41,12 +50,21 @@ gitretro v0.1.0
 
 COMMANDS
 run         {}
+resend <id> {}
 rund        {}
 installd    {}
 config      {}
 help        {}
+
+RUN OPTIONS
+--format md|html|json   prints the report instead of sending it to slack
+--no-fetch              analyses the local refs without fetching the remote first
+--from-ref <ref>        with --to-ref, release notes of the commits between two tags or commits
+--to-ref <ref>          instead of the last two weeks
+--scope <name>          reports only this scope of the config, needed to export one of several
 "#,
-                Command::Run.help(),
+                Command::Run(RunOptions::default()).help(),
+                Command::Resend(String::new()).help(),
                 Command::RunD.help(),
                 Command::InstallD.help(),
                 Command::Config.help(),
@@ -57,6 +75,36 @@ help        {}
     }
 }
 
+#[derive(Default)]
+pub struct RunOptions {
+    pub format: Format,
+    pub no_fetch: bool,
+    pub from_ref: Option<String>,
+    pub to_ref: Option<String>,
+    /// Only this scope of the config is reported, see `config::Scope`
+    pub scope: Option<String>,
+}
+
+impl RunOptions {
+    fn parse(mut args: impl Iterator<Item = String>) -> Option<RunOptions> {
+        let mut options = RunOptions::default();
+        while let Some(arg) = args.next() {
+            match &arg[..] {
+                "--format" => options.format = args.next()?.parse().ok()?,
+                "--no-fetch" => options.no_fetch = true,
+                "--from-ref" => options.from_ref = Some(args.next()?),
+                "--to-ref" => options.to_ref = Some(args.next()?),
+                "--scope" => options.scope = Some(args.next()?),
+                _ => return None,
+            }
+        }
+        // a release needs both ends
+        if options.from_ref.is_some() != options.to_ref.is_some() {
+            return None;
+        }
+        Some(options)
+    }
+}
 
 pub fn configure() -> DynErrResult<Config> {
     log::important("Repository absolute path:");
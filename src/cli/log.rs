@@ -25,13 +25,18 @@ where
 }
 
 pub fn message<S: AsRef<str>>(msg: S) {
-    println!("{}", msg.as_ref().color(MESSAGE_COLOR));
+    eprintln!("{}", msg.as_ref().color(MESSAGE_COLOR));
 }
 
 pub fn important<S: AsRef<str>>(msg: S) {
-    println!("{}", msg.as_ref().bold());
+    eprintln!("{}", msg.as_ref().bold());
 }
 
 pub fn error<S: AsRef<str>>(msg: S) {
-    println!("{}", msg.as_ref().color(ERROR_COLOR));
+    eprintln!("{}", msg.as_ref().color(ERROR_COLOR));
+}
+
+/// Plain stdout, the progress messages go to stderr so the output can be piped
+pub fn output<S: AsRef<str>>(msg: S) {
+    println!("{}", msg.as_ref());
 }
@@ -1,10 +1,6 @@
-use super::log;
-use crate::{
-    config, fs::get_savedata_file, git, git::RepoAnalyzer, slack, DynErrResult,
-};
-use config::Config;
+use super::RunOptions;
+use crate::{fs::get_savedata_file, DynErrResult};
 use confy::{load_path, store_path};
-use git::search_interval::SearchInterval;
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize, Default)]
@@ -17,28 +13,7 @@ pub fn run() -> DynErrResult<()> {
     if did_run {
         return write_did_run(RunDaemon { did_run: false });
     }
-    let app_config = Config::load()?;
-    log::multiple(vec![
-        log::Style::Message("Config: "),
-        log::Style::Important(&app_config.to_string()),
-    ]);
-    let repo = RepoAnalyzer::new(&app_config.repo_path)?;
-    let SearchInterval { from, to } = repo.interval;
-    log::multiple(vec![
-        log::Style::Message("Searching logs from: "),
-        log::Style::Important(&from.to_string()),
-        log::Style::Message(" to "),
-        log::Style::Important(&to.to_string()),
-    ]);
-    let commits = repo.get_commits()?;
-    let branches = repo.get_in_progress()?;
-    let message = slack::MessageIngredients {
-        branches,
-        commits,
-        interval: repo.interval
-    };
-    let response = message.send_to_slack(app_config.slack_web_hook, message.format_slack())?;
-    log::message(format!("{:?}", &response));
+    crate::run(&RunOptions::default())?;
     write_did_run(RunDaemon { did_run: true })
 }
 
@@ -1,12 +1,189 @@
 use crate::{fs::get_config_file, DynErrResult};
 use confy::{load_path, store_path};
 use serde::{Deserialize, Serialize};
-use std::{fmt::Display, path::PathBuf};
+use chrono::Duration;
+use std::{collections::BTreeMap, error::Error, fmt::Display, fs, path::PathBuf};
 
-#[derive(Serialize, Deserialize)]
+/// Branches without commits for longer than this are stale, unless `stale_branch_days` is set
+const DEFAULT_STALE_DAYS: i64 = 7;
+
+/// Below this a slack message can't even hold one line of the report
+const MIN_MESSAGE_LENGTH: usize = 200;
+
+#[derive(Debug)]
+pub enum ConfigError {
+    SlackMaxLength(usize),
+    /// The mirror of `repo_url` has a single remote, it can't stand for several
+    MirrorRemotes(Vec<String>),
+}
+
+impl Error for ConfigError {}
+
+impl Display for ConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfigError::SlackMaxLength(length) => write!(
+                f,
+                "slack_max_length is {}, it should be at least {}",
+                length, MIN_MESSAGE_LENGTH
+            ),
+            ConfigError::MirrorRemotes(remotes) => write!(
+                f,
+                "repo_url is mirrored into one remote, remotes lists {}",
+                remotes.join(", ")
+            ),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
 pub struct Config {
     pub repo_path: String,
     pub slack_web_hook: String,
+    /// Analyse a bare clone of this url kept in the cache directory, instead of `repo_path`
+    pub repo_url: Option<String>,
+    #[serde(default)]
+    pub diff_stats: bool,
+    pub commit_url_template: Option<String>,
+    pub branch_url_template: Option<String>,
+    pub pull_request_url_template: Option<String>,
+    pub tag_url_template: Option<String>,
+    pub issue_pattern: Option<String>,
+    pub issue_url_template: Option<String>,
+    /// Handlebars template used instead of the default slack layout
+    pub template_path: Option<String>,
+    /// Bot token for the slack api, used instead of the web hook together with `slack_channel`
+    pub slack_bot_token: Option<String>,
+    pub slack_channel: Option<String>,
+    /// Defaults to https://slack.com/api
+    pub slack_api_url: Option<String>,
+    /// Messages longer than this are split, defaults to slack's 4000 characters
+    pub slack_max_length: Option<usize>,
+    #[serde(default)]
+    pub slack_oversized: SlackOversized,
+    #[serde(default)]
+    pub slack_mentions: SlackMentions,
+    pub stale_branch_days: Option<i64>,
+    /// Report local branches and unpushed commits too, for a retro of your own clone
+    #[serde(default)]
+    pub include_local: bool,
+    /// Never fetch, like `run --no-fetch`, for clones which are kept up to date by other means
+    #[serde(default)]
+    pub offline: bool,
+    /// Remotes to fetch and scan, the first one holds the mainline, like `["upstream", "alice"]`
+    #[serde(default = "Config::default_remotes")]
+    pub remotes: Vec<String>,
+    /// Glob of the tags reported as releases, like `v*`, every tag by default
+    pub tag_pattern: Option<String>,
+    // toml wants the tables after the plain values, keep them last
+    /// Slack user ids by git author email or name, like `"alice@example.com" = "U123"`
+    #[serde(default)]
+    pub slack_users: BTreeMap<String, String>,
+    #[serde(default)]
+    pub git_auth: GitAuth,
+    /// Git author name owning each personal remote, like `alice = "Alice"`
+    #[serde(default)]
+    pub remote_owners: BTreeMap<String, String>,
+    #[serde(default)]
+    pub exclude: Exclude,
+    /// One report per scope, by name, instead of one for the whole repository
+    #[serde(default)]
+    pub scopes: BTreeMap<String, Scope>,
+    pub smtp: Option<SmtpConfig>,
+}
+
+/// How fetch authenticates. Without any of these it tries the ssh agent,
+/// then the usual keys in `~/.ssh`, then git's credential helper
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct GitAuth {
+    /// Private key used instead of `~/.ssh/id_ed25519`, `id_ecdsa` or `id_rsa`
+    pub ssh_key: Option<String>,
+    pub ssh_passphrase: Option<String>,
+    /// For https remotes, gitlab accepts any username with a token, like `oauth2`
+    pub username: Option<String>,
+    /// Personal access token, sent as the password over https
+    pub token: Option<String>,
+}
+
+/// Commits left out of the report, like those of dependabot or version bumps.
+/// A commit matching any of the rules is left out
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct Exclude {
+    /// Regexes of author names, like `\[bot\]$`
+    pub authors: Vec<String>,
+    /// Author emails, compared ignoring case
+    pub emails: Vec<String>,
+    /// Regexes of the whole commit message, like `^chore\(release\)`
+    pub messages: Vec<String>,
+    /// Git pathspecs like `Cargo.lock` or `*.lock`, for commits touching nothing else
+    pub paths: Vec<String>,
+}
+
+/// The directories of a team in a monorepo, reported on their own
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct Scope {
+    /// Git pathspecs like `services/payments` or `libs/*/billing`
+    pub paths: Vec<String>,
+    /// Where the bot posts the scope's report instead of `slack_channel`
+    pub slack_channel: Option<String>,
+    /// Where the scope's report is sent instead of `slack_web_hook`
+    pub slack_web_hook: Option<String>,
+}
+
+/// Email delivery, the report is sent as html with a plain text alternative
+#[derive(Serialize, Deserialize, Clone)]
+pub struct SmtpConfig {
+    pub host: String,
+    #[serde(default = "SmtpConfig::default_port")]
+    pub port: u16,
+    #[serde(default)]
+    pub security: SmtpSecurity,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    pub from: String,
+    pub to: Vec<String>,
+}
+
+impl SmtpConfig {
+    fn default_port() -> u16 {
+        587
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum SmtpSecurity {
+    /// Plain connection upgraded with STARTTLS, usually on port 587
+    #[default]
+    StartTls,
+    /// TLS from the start, usually on port 465
+    Tls,
+    /// No encryption at all, only for local relays
+    None,
+}
+
+/// What to do with a report too long for a single slack message
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SlackOversized {
+    /// Several messages, cut between authors
+    #[default]
+    Split,
+    /// The summary as a message and the whole report as an attached file, needs the bot token
+    File,
+}
+
+/// Who gets a `<@U123>` mention in the heading of their section
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SlackMentions {
+    /// Every author found in `slack_users`
+    #[default]
+    All,
+    /// Only the authors with a stale branch
+    Stale,
 }
 
 impl Config {
@@ -14,12 +191,57 @@ impl Config {
         Self {
             repo_path: repo_path.into(),
             slack_web_hook: slack_web_hook.into(),
+            ..Default::default()
         }
     }
 
     pub fn load() -> DynErrResult<Config> {
         let file_path = Config::get_file_path()?;
-        load_path(file_path.as_path()).map_err(Box::from)
+        let config: Config = load_path(file_path.as_path())?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Catches the values which would only fail, or panic, in the middle of a run
+    fn validate(&self) -> Result<(), ConfigError> {
+        match self.slack_max_length {
+            Some(length) if length < MIN_MESSAGE_LENGTH => {
+                return Err(ConfigError::SlackMaxLength(length))
+            }
+            _ => {}
+        }
+        if self.repo_url.is_some() && self.remotes.len() > 1 {
+            return Err(ConfigError::MirrorRemotes(self.remotes.clone()));
+        }
+        Ok(())
+    }
+
+    /// Contents of the user's report template, if one is configured
+    pub fn load_template(&self) -> DynErrResult<Option<String>> {
+        match &self.template_path {
+            Some(path) => fs::read_to_string(path).map(Some).map_err(Box::from),
+            None => Ok(None),
+        }
+    }
+
+    fn default_remotes() -> Vec<String> {
+        vec!["origin".into()]
+    }
+
+    pub fn stale_after(&self) -> Duration {
+        Duration::days(self.stale_branch_days.unwrap_or(DEFAULT_STALE_DAYS))
+    }
+
+    /// The config of a scope's report, sent to the scope's channel or web hook if it has one
+    pub fn scoped(&self, scope: &Scope) -> Config {
+        let mut config = self.clone();
+        if let Some(channel) = &scope.slack_channel {
+            config.slack_channel = Some(channel.clone());
+        }
+        if let Some(hook) = &scope.slack_web_hook {
+            config.slack_web_hook = hook.clone();
+        }
+        config
     }
 
     pub fn store(&self) -> DynErrResult<()> {
@@ -38,11 +260,73 @@ impl Config {
 
 impl Display for Config {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let string = format!(
-            "{{\n  repo_path: {}\n  slack_web_hook: {}\n}}",
-            self.repo_path, self.slack_web_hook
-        );
-        f.write_str(&string)
+        let optional = |value: &Option<String>| value.clone().unwrap_or_else(|| "-".into());
+        writeln!(f, "{{")?;
+        writeln!(f, "  repo_path: {}", self.repo_path)?;
+        writeln!(f, "  slack_web_hook: {}", self.slack_web_hook)?;
+        writeln!(f, "  repo_url: {}", optional(&self.repo_url))?;
+        writeln!(f, "  diff_stats: {}", self.diff_stats)?;
+        writeln!(f, "  commit_url_template: {}", optional(&self.commit_url_template))?;
+        writeln!(f, "  branch_url_template: {}", optional(&self.branch_url_template))?;
+        writeln!(
+            f,
+            "  pull_request_url_template: {}",
+            optional(&self.pull_request_url_template)
+        )?;
+        writeln!(f, "  tag_url_template: {}", optional(&self.tag_url_template))?;
+        writeln!(f, "  issue_pattern: {}", optional(&self.issue_pattern))?;
+        writeln!(f, "  issue_url_template: {}", optional(&self.issue_url_template))?;
+        writeln!(f, "  template_path: {}", optional(&self.template_path))?;
+        // the config is logged on every run, secrets only show whether they are set
+        writeln!(
+            f,
+            "  slack_bot_token: {}",
+            if self.slack_bot_token.is_some() { "set" } else { "-" }
+        )?;
+        writeln!(f, "  slack_channel: {}", optional(&self.slack_channel))?;
+        writeln!(f, "  slack_api_url: {}", optional(&self.slack_api_url))?;
+        match self.slack_max_length {
+            Some(length) => writeln!(f, "  slack_max_length: {}", length)?,
+            None => writeln!(f, "  slack_max_length: -")?,
+        }
+        writeln!(f, "  slack_oversized: {:?}", self.slack_oversized)?;
+        writeln!(f, "  slack_mentions: {:?}", self.slack_mentions)?;
+        writeln!(f, "  stale_branch_days: {}", self.stale_after().num_days())?;
+        writeln!(f, "  include_local: {}", self.include_local)?;
+        writeln!(f, "  offline: {}", self.offline)?;
+        writeln!(f, "  remotes: {}", self.remotes.join(", "))?;
+        writeln!(f, "  tag_pattern: {}", optional(&self.tag_pattern))?;
+        writeln!(f, "  slack_users: {}", self.slack_users.len())?;
+        writeln!(
+            f,
+            "  git_auth: ssh_key {}, username {}, token {}",
+            optional(&self.git_auth.ssh_key),
+            optional(&self.git_auth.username),
+            if self.git_auth.token.is_some() { "set" } else { "-" }
+        )?;
+        writeln!(f, "  remote_owners: {}", self.remote_owners.len())?;
+        writeln!(
+            f,
+            "  exclude: {} authors, {} emails, {} messages, {} paths",
+            self.exclude.authors.len(),
+            self.exclude.emails.len(),
+            self.exclude.messages.len(),
+            self.exclude.paths.len()
+        )?;
+        let scopes: Vec<&str> = self.scopes.keys().map(String::as_str).collect();
+        writeln!(f, "  scopes: {}", scopes.join(", "))?;
+        match &self.smtp {
+            Some(smtp) => writeln!(
+                f,
+                "  smtp: {}:{} ({:?}) to {}",
+                smtp.host,
+                smtp.port,
+                smtp.security,
+                smtp.to.join(", ")
+            )?,
+            None => writeln!(f, "  smtp: -")?,
+        }
+        write!(f, "}}")
     }
 }
 
@@ -51,6 +335,77 @@ impl Default for Config {
         Self {
             repo_path: "".into(),
             slack_web_hook: "".into(),
+            repo_url: None,
+            diff_stats: false,
+            commit_url_template: None,
+            branch_url_template: None,
+            pull_request_url_template: None,
+            tag_url_template: None,
+            issue_pattern: None,
+            issue_url_template: None,
+            template_path: None,
+            slack_bot_token: None,
+            slack_channel: None,
+            slack_api_url: None,
+            slack_max_length: None,
+            slack_oversized: SlackOversized::default(),
+            slack_mentions: SlackMentions::default(),
+            stale_branch_days: None,
+            include_local: false,
+            offline: false,
+            remotes: Config::default_remotes(),
+            tag_pattern: None,
+            slack_users: BTreeMap::new(),
+            git_auth: GitAuth::default(),
+            remote_owners: BTreeMap::new(),
+            exclude: Exclude::default(),
+            scopes: BTreeMap::new(),
+            smtp: None,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Config;
+
+    #[test]
+    fn test_display_masks_bot_token() {
+        let config = Config {
+            slack_bot_token: Some("xoxb-secret".into()),
+            ..Config::default()
+        };
+        let display = config.to_string();
+        assert!(display.contains("slack_bot_token: set"));
+        assert!(!display.contains("xoxb-secret"));
+    }
+
+    #[test]
+    fn test_slack_max_length_too_small() {
+        let mut config = Config {
+            slack_max_length: Some(0),
+            ..Config::default()
+        };
+        assert_eq!(
+            config.validate().unwrap_err().to_string(),
+            "slack_max_length is 0, it should be at least 200"
+        );
+        config.slack_max_length = Some(3000);
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_mirror_remotes() {
+        let mut config = Config {
+            repo_url: Some("git@github.com:team/pay.git".into()),
+            remotes: vec!["origin".into(), "upstream".into()],
+            ..Config::default()
+        };
+        assert_eq!(
+            config.validate().unwrap_err().to_string(),
+            "repo_url is mirrored into one remote, remotes lists origin, upstream"
+        );
+        config.remotes = vec!["upstream".into()];
+        assert!(config.validate().is_ok());
+    }
+}
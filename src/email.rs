@@ -0,0 +1,154 @@
+use crate::{
+    cli::log,
+    config::{SmtpConfig, SmtpSecurity},
+    report::{export, Format, Report},
+    DynErrResult,
+};
+use lettre::{
+    smtp::authentication::Credentials, ClientSecurity, ClientTlsParameters, SmtpClient, Transport,
+};
+use lettre_email::EmailBuilder;
+use native_tls::TlsConnector;
+use serde::{Deserialize, Serialize};
+
+/// The rendered email, kept in history next to the slack payload
+#[derive(Serialize, Deserialize, Clone)]
+pub struct EmailMessage {
+    pub subject: String,
+    pub html: String,
+    pub text: String,
+}
+
+impl EmailMessage {
+    pub fn new(report: &Report) -> DynErrResult<Self> {
+        Ok(Self {
+            subject: match &report.scope {
+                Some(scope) => format!(
+                    "Team git-status of {} from {} to {}",
+                    scope, report.from, report.to
+                ),
+                None => format!("Team git-status from {} to {}", report.from, report.to),
+            },
+            html: export(report, Format::Html)?,
+            text: export(report, Format::Markdown)?,
+        })
+    }
+}
+
+pub fn send_email(smtp: &SmtpConfig, message: &EmailMessage) -> DynErrResult<()> {
+    let mut builder = EmailBuilder::new()
+        .from(smtp.from.as_str())
+        .subject(message.subject.as_str())
+        .alternative(message.html.as_str(), message.text.as_str());
+    for recipient in &smtp.to {
+        builder = builder.to(recipient.as_str());
+    }
+    let email = builder.build()?;
+
+    let security = match smtp.security {
+        SmtpSecurity::None => ClientSecurity::None,
+        SmtpSecurity::StartTls => ClientSecurity::Required(ClientTlsParameters::new(
+            smtp.host.clone(),
+            TlsConnector::new()?,
+        )),
+        SmtpSecurity::Tls => ClientSecurity::Wrapper(ClientTlsParameters::new(
+            smtp.host.clone(),
+            TlsConnector::new()?,
+        )),
+    };
+    let mut client = SmtpClient::new((smtp.host.as_str(), smtp.port), security)?;
+    if let (Some(username), Some(password)) = (&smtp.username, &smtp.password) {
+        client = client.credentials(Credentials::new(username.clone(), password.clone()));
+    }
+    log::message(format!(
+        "Sending email to {} through {}:{}",
+        smtp.to.join(", "),
+        smtp.host,
+        smtp.port
+    ));
+    client.transport().send(email.into())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{send_email, EmailMessage};
+    use crate::{
+        config::{SmtpConfig, SmtpSecurity},
+        report::fixtures,
+    };
+    use std::{
+        io::{BufRead, BufReader, Write},
+        net::TcpListener,
+        thread,
+    };
+
+    /// Just enough of a SMTP server to accept one email, returns what was sent after DATA
+    fn smtp_stand_in() -> (u16, thread::JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let handle = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut writer = stream.try_clone().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut data = String::new();
+            let mut in_data = false;
+            writer.write_all(b"220 localhost ESMTP\r\n").unwrap();
+            loop {
+                let mut line = String::new();
+                if reader.read_line(&mut line).unwrap() == 0 {
+                    break;
+                }
+                if in_data {
+                    if line == ".\r\n" {
+                        in_data = false;
+                        writer.write_all(b"250 queued\r\n").unwrap();
+                    } else {
+                        data.push_str(&line);
+                    }
+                    continue;
+                }
+                let command = line.to_uppercase();
+                if command.starts_with("EHLO") {
+                    writer
+                        .write_all(b"250-localhost\r\n250 8BITMIME\r\n")
+                        .unwrap();
+                } else if command.starts_with("DATA") {
+                    in_data = true;
+                    writer.write_all(b"354 go ahead\r\n").unwrap();
+                } else if command.starts_with("QUIT") {
+                    writer.write_all(b"221 bye\r\n").unwrap();
+                    break;
+                } else {
+                    writer.write_all(b"250 ok\r\n").unwrap();
+                }
+            }
+            data
+        });
+        (port, handle)
+    }
+
+    #[test]
+    fn test_send_email() {
+        let (port, server) = smtp_stand_in();
+        let smtp = SmtpConfig {
+            host: "127.0.0.1".into(),
+            port,
+            security: SmtpSecurity::None,
+            username: None,
+            password: None,
+            from: "retro@example.com".into(),
+            to: vec!["lead@example.com".into(), "po@example.com".into()],
+        };
+        let message = EmailMessage::new(&fixtures::ingredients().report()).unwrap();
+        send_email(&smtp, &message).unwrap();
+
+        let data = server.join().unwrap();
+        assert!(data.contains("Subject: Team git-status from 2020-05-23 22:00:00"));
+        assert!(data.contains("lead@example.com"));
+        assert!(data.contains("po@example.com"));
+        assert!(data.contains("multipart/alternative"));
+        assert!(data.contains("text/html"));
+        assert!(data.contains("text/plain"));
+    }
+}
@@ -25,6 +25,14 @@ pub fn get_savedata_file() -> DynErrResult<PathBuf> {
     get_config_file(name)
 }
 
+pub fn get_history_file(id: impl AsRef<str>) -> DynErrResult<PathBuf> {
+    #[cfg(feature = "production")]
+    let dir = "history";
+    #[cfg(not(feature = "production"))]
+    let dir = "history_dev";
+    get_config_file(format!("{}/{}", dir, id.as_ref()))
+}
+
 pub fn get_config_file(name: impl AsRef<str>) -> DynErrResult<PathBuf> {
     let dirs = UserDirs::new().ok_or(FsError::UserHome)?;
     let path = dirs.home_dir();
@@ -36,6 +44,27 @@ pub fn get_config_file(name: impl AsRef<str>) -> DynErrResult<PathBuf> {
     Ok(path_buf)
 }
 
+/// Where the bare clone of `url` is kept, `~/.cache/git-retrospective/mirrors/<url>.git`
+pub fn get_mirror_dir(url: impl AsRef<str>) -> DynErrResult<PathBuf> {
+    #[cfg(feature = "production")]
+    let dir = "mirrors";
+    #[cfg(not(feature = "production"))]
+    let dir = "mirrors_dev";
+    let dirs = UserDirs::new().ok_or(FsError::UserHome)?;
+    let name: String = url
+        .as_ref()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' { c } else { '_' })
+        .collect();
+    let mut path_buf = dirs.home_dir().to_path_buf();
+    path_buf.push(".cache");
+    path_buf.push(super::APP_NAME);
+    path_buf.push(dir);
+    path_buf.push(name);
+    path_buf.set_extension("git");
+    Ok(path_buf)
+}
+
 pub fn get_launch_agent_file(name: impl AsRef<str>) -> DynErrResult<PathBuf> {
     let dirs = UserDirs::new().ok_or(FsError::UserHome)?;
     let mut path_buf = dirs.home_dir().to_path_buf();
@@ -1,4 +1,16 @@
+mod analyzer_options;
+mod conventional;
+mod credentials;
+mod noise;
+mod pull_request;
 mod repo_analyzer;
 pub mod search_interval;
+mod web_links;
 
-pub use repo_analyzer::{RepoAnalyzer, WorkingBranch, RetroCommit};
+pub use analyzer_options::AnalyzerOptions;
+pub use conventional::{Category, ConventionalCommit};
+pub use pull_request::PullRequest;
+pub use repo_analyzer::{
+    BranchStatus, Contributor, DiffStats, Release, RepoAnalyzer, RetroCommit, WorkingBranch,
+};
+pub use web_links::WebLinks;
@@ -0,0 +1,50 @@
+use crate::config::{Config, Exclude, GitAuth};
+use std::collections::BTreeMap;
+
+/// Knobs which change what `RepoAnalyzer` collects, usually taken from the `Config`
+#[derive(Clone, Default)]
+pub struct AnalyzerOptions {
+    /// Compute insertions/deletions per commit, it's slow on big repos
+    pub diff_stats: bool,
+    /// Overrides the commit link guessed from the `origin` url, `{commit}` is the placeholder
+    pub commit_url_template: Option<String>,
+    /// Overrides the branch link guessed from the `origin` url, `{branch}` is the placeholder
+    pub branch_url_template: Option<String>,
+    /// Overrides the pull request link guessed from the `origin` url, `{number}` is the placeholder
+    pub pull_request_url_template: Option<String>,
+    /// Overrides the release link guessed from the `origin` url, `{tag}` is the placeholder
+    pub tag_url_template: Option<String>,
+    /// Also report local branches and commits which were not pushed yet
+    pub include_local: bool,
+    pub auth: GitAuth,
+    /// Remotes to fetch and scan, the first one holds the mainline, `origin` when empty
+    pub remotes: Vec<String>,
+    /// Owner of the branches of a remote, by remote, like `alice = "Alice Smith"` for her fork
+    pub remote_owners: BTreeMap<String, String>,
+    /// Only the tags matching this glob, like `v*`, are releases. All tags when empty
+    pub tag_pattern: Option<String>,
+    /// Rules for the bot and noise commits left out of the commits and branches
+    pub exclude: Exclude,
+    /// Only commits and branches changing these pathspecs are reported, see `config::Scope`.
+    /// The whole repository when empty
+    pub paths: Vec<String>,
+}
+
+impl From<&Config> for AnalyzerOptions {
+    fn from(config: &Config) -> Self {
+        Self {
+            diff_stats: config.diff_stats,
+            commit_url_template: config.commit_url_template.clone(),
+            branch_url_template: config.branch_url_template.clone(),
+            pull_request_url_template: config.pull_request_url_template.clone(),
+            tag_url_template: config.tag_url_template.clone(),
+            include_local: config.include_local,
+            auth: config.git_auth.clone(),
+            remotes: config.remotes.clone(),
+            remote_owners: config.remote_owners.clone(),
+            tag_pattern: config.tag_pattern.clone(),
+            exclude: config.exclude.clone(),
+            paths: vec![],
+        }
+    }
+}
@@ -0,0 +1,122 @@
+use serde::Serialize;
+
+/// Report section of a commit, in the order the sections are displayed
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Category {
+    Breaking,
+    Feature,
+    Fix,
+    Chore,
+    Other,
+}
+
+impl Category {
+    pub fn title(&self) -> &'static str {
+        match self {
+            Category::Breaking => "Breaking changes",
+            Category::Feature => "Features",
+            Category::Fix => "Fixes",
+            Category::Chore => "Chores",
+            Category::Other => "Other",
+        }
+    }
+}
+
+/// A summary following https://www.conventionalcommits.org like `feat(api)!: drop v1`
+#[derive(Debug, Eq, PartialEq)]
+pub struct ConventionalCommit<'a> {
+    pub kind: &'a str,
+    pub scope: Option<&'a str>,
+    pub breaking: bool,
+    pub description: &'a str,
+}
+
+impl<'a> ConventionalCommit<'a> {
+    pub fn parse(summary: &'a str) -> Option<Self> {
+        let colon = summary.find(':')?;
+        let (prefix, description) = (&summary[..colon], summary[colon + 1..].trim());
+        let (prefix, breaking) = match prefix.strip_suffix('!') {
+            Some(prefix) => (prefix, true),
+            None => (prefix, false),
+        };
+        let (kind, scope) = match prefix.find('(') {
+            Some(open) => {
+                let scope = prefix[open + 1..].strip_suffix(')')?;
+                (&prefix[..open], Some(scope))
+            }
+            None => (prefix, None),
+        };
+        let is_word = !kind.is_empty() && kind.chars().all(|c| c.is_ascii_alphabetic());
+        if !is_word || description.is_empty() || scope == Some("") {
+            return None;
+        }
+        Some(Self {
+            kind,
+            scope,
+            breaking,
+            description,
+        })
+    }
+
+    pub fn category(&self) -> Category {
+        if self.breaking {
+            return Category::Breaking;
+        }
+        match &self.kind.to_lowercase()[..] {
+            "feat" | "feature" => Category::Feature,
+            "fix" | "bugfix" | "hotfix" => Category::Fix,
+            "chore" | "build" | "ci" | "docs" | "style" | "refactor" | "perf" | "test"
+            | "revert" => Category::Chore,
+            _ => Category::Other,
+        }
+    }
+}
+
+/// The footer which marks a breaking change even without the `!` in the summary
+pub fn has_breaking_footer(body: &str) -> bool {
+    body.lines()
+        .any(|line| line.starts_with("BREAKING CHANGE:") || line.starts_with("BREAKING-CHANGE:"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{has_breaking_footer, Category, ConventionalCommit};
+
+    #[test]
+    fn test_parse_conventional() {
+        assert_eq!(
+            ConventionalCommit::parse("feat(api)!: drop v1 endpoints"),
+            Some(ConventionalCommit {
+                kind: "feat",
+                scope: Some("api"),
+                breaking: true,
+                description: "drop v1 endpoints",
+            })
+        );
+        let fix = ConventionalCommit::parse("fix: crash on start").unwrap();
+        assert_eq!(fix.scope, None);
+        assert_eq!(fix.category(), Category::Fix);
+        let docs = ConventionalCommit::parse("docs(readme): typo").unwrap();
+        assert_eq!(docs.category(), Category::Chore);
+    }
+
+    #[test]
+    fn test_parse_non_conventional() {
+        assert_eq!(
+            ConventionalCommit::parse("Add dev and production environment"),
+            None
+        );
+        assert_eq!(ConventionalCommit::parse("Merge branch: master"), None);
+        assert_eq!(ConventionalCommit::parse("feat(): empty scope"), None);
+        assert_eq!(ConventionalCommit::parse("feat:"), None);
+    }
+
+    #[test]
+    fn test_breaking_footer() {
+        assert!(has_breaking_footer(
+            "feat: x\n\nBREAKING CHANGE: config moved"
+        ));
+        assert!(!has_breaking_footer("feat: x\n\nnot breaking"));
+    }
+}
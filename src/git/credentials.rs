@@ -0,0 +1,161 @@
+use crate::config::GitAuth;
+use directories::UserDirs;
+use git2::{Config, Cred, CredentialType, Error};
+use std::{env, path::PathBuf};
+
+/// Looked up in `~/.ssh` when no `ssh_key` is configured, in this order
+const DEFAULT_KEYS: [&str; 3] = ["id_ed25519", "id_ecdsa", "id_rsa"];
+
+/// Answers the credential requests of a fetch. libgit2 asks again after every rejected
+/// credential, so each way is tried once and then it fails instead of looping forever
+pub struct Credentials {
+    auth: GitAuth,
+    /// libgit2 aborts the fetch when it can't reach an agent, instead of asking for
+    /// another credential, so the agent is only tried when `SSH_AUTH_SOCK` is set
+    agent: bool,
+    tried_agent: bool,
+    /// Keys not tried yet, the next one is last
+    keys: Vec<PathBuf>,
+    tried_token: bool,
+    tried_helper: bool,
+    tried_default: bool,
+}
+
+impl Credentials {
+    pub fn new(auth: GitAuth) -> Self {
+        let mut keys = match &auth.ssh_key {
+            Some(key) => vec![expand_home(key)],
+            None => home()
+                .map(|home| {
+                    DEFAULT_KEYS
+                        .iter()
+                        .map(|key| home.join(".ssh").join(key))
+                        .filter(|key| key.exists())
+                        .collect()
+                })
+                .unwrap_or_default(),
+        };
+        keys.reverse();
+        Self {
+            auth,
+            agent: env::var_os("SSH_AUTH_SOCK").is_some(),
+            tried_agent: false,
+            keys,
+            tried_token: false,
+            tried_helper: false,
+            tried_default: false,
+        }
+    }
+
+    /// Next credential allowed for `url`, an error once everything was rejected
+    pub fn next(
+        &mut self,
+        url: &str,
+        username_from_url: Option<&str>,
+        allowed: CredentialType,
+    ) -> Result<Cred, Error> {
+        let username = username_from_url
+            .map(String::from)
+            .or_else(|| self.auth.username.clone());
+        let username = username.as_deref();
+        if allowed.contains(CredentialType::USERNAME) {
+            return Cred::username(username.unwrap_or("git"));
+        }
+        if allowed.contains(CredentialType::SSH_KEY) {
+            let username = username.unwrap_or("git");
+            // a configured key is meant to be used, the agent could offer another identity
+            if self.agent && !self.tried_agent && self.auth.ssh_key.is_none() {
+                self.tried_agent = true;
+                return Cred::ssh_key_from_agent(username);
+            }
+            if let Some(key) = self.keys.pop() {
+                return Cred::ssh_key(username, None, &key, self.auth.ssh_passphrase.as_deref());
+            }
+        }
+        if allowed.contains(CredentialType::USER_PASS_PLAINTEXT) {
+            if let (Some(token), false) = (&self.auth.token, self.tried_token) {
+                self.tried_token = true;
+                return Cred::userpass_plaintext(username.unwrap_or("git"), token);
+            }
+            if !self.tried_helper {
+                self.tried_helper = true;
+                let config = Config::open_default()?;
+                return Cred::credential_helper(&config, url, username);
+            }
+        }
+        if allowed.contains(CredentialType::DEFAULT) && !self.tried_default {
+            self.tried_default = true;
+            return Cred::default();
+        }
+        Err(Error::from_str(&format!(
+            "No credentials left to try for {}, check git_auth in the config",
+            url
+        )))
+    }
+}
+
+fn home() -> Option<PathBuf> {
+    UserDirs::new().map(|dirs| dirs.home_dir().to_path_buf())
+}
+
+/// `~/.ssh/work_key` is relative to the user's home, like in a shell
+fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), home()) {
+        (Some(relative), Some(home)) => home.join(relative),
+        _ => PathBuf::from(path),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Credentials;
+    use crate::config::GitAuth;
+    use git2::CredentialType;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_configured_key_is_tried_once() {
+        let mut credentials = Credentials::new(GitAuth {
+            ssh_key: Some("/keys/work_ed25519".into()),
+            ssh_passphrase: Some("secret".into()),
+            ..GitAuth::default()
+        });
+        let url = "git@gitlab.example.com:team/pay.git";
+        let key = credentials.next(url, Some("git"), CredentialType::SSH_KEY);
+        assert_eq!(key.unwrap().credtype(), CredentialType::SSH_KEY.bits());
+        assert!(credentials
+            .next(url, Some("git"), CredentialType::SSH_KEY)
+            .is_err());
+    }
+
+    #[test]
+    fn test_keys_without_agent() {
+        // like under launchd or cron, where no agent is running
+        let mut credentials = Credentials::new(GitAuth::default());
+        credentials.agent = false;
+        credentials.keys = vec![PathBuf::from("/home/me/.ssh/id_rsa")];
+        let url = "git@github.com:team/pay.git";
+        let key = credentials.next(url, Some("git"), CredentialType::SSH_KEY);
+        assert_eq!(key.unwrap().credtype(), CredentialType::SSH_KEY.bits());
+        assert!(credentials.keys.is_empty());
+        assert!(!credentials.tried_agent);
+    }
+
+    #[test]
+    fn test_https_token() {
+        let mut credentials = Credentials::new(GitAuth {
+            username: Some("oauth2".into()),
+            token: Some("glpat-token".into()),
+            ..GitAuth::default()
+        });
+        let token = credentials.next(
+            "https://gitlab.example.com/team/pay.git",
+            None,
+            CredentialType::USER_PASS_PLAINTEXT,
+        );
+        assert_eq!(
+            token.unwrap().credtype(),
+            CredentialType::USER_PASS_PLAINTEXT.bits()
+        );
+    }
+}
@@ -0,0 +1,126 @@
+use crate::{config::Exclude, DynErrResult};
+use git2::{Commit, Pathspec, PathspecFlags, Repository};
+use regex::Regex;
+
+/// Recognises the commits of bots and other noise, following the `Exclude` rules
+#[derive(Default)]
+pub struct NoiseFilter {
+    authors: Vec<Regex>,
+    emails: Vec<String>,
+    messages: Vec<Regex>,
+    paths: Option<Pathspec>,
+}
+
+impl NoiseFilter {
+    /// Fails on an invalid regex or pathspec
+    pub fn new(exclude: &Exclude) -> DynErrResult<Self> {
+        let regexes = |patterns: &[String]| -> Result<Vec<Regex>, regex::Error> {
+            patterns.iter().map(|pattern| Regex::new(pattern)).collect()
+        };
+        let paths = if exclude.paths.is_empty() {
+            None
+        } else {
+            Some(Pathspec::new(exclude.paths.iter())?)
+        };
+        Ok(Self {
+            authors: regexes(&exclude.authors)?,
+            emails: exclude
+                .emails
+                .iter()
+                .map(|email| email.to_lowercase())
+                .collect(),
+            messages: regexes(&exclude.messages)?,
+            paths,
+        })
+    }
+
+    pub fn is_noise(&self, repo: &Repository, commit: &Commit) -> DynErrResult<bool> {
+        let author = commit.author();
+        let name = author.name().unwrap_or_default();
+        let email = author.email().unwrap_or_default().to_lowercase();
+        let message = commit.message().unwrap_or_default();
+        if self.authors.iter().any(|pattern| pattern.is_match(name))
+            || self.emails.contains(&email)
+            || self
+                .messages
+                .iter()
+                .any(|pattern| pattern.is_match(message))
+        {
+            return Ok(true);
+        }
+        match &self.paths {
+            Some(paths) => only_touches(repo, commit, paths),
+            None => Ok(false),
+        }
+    }
+}
+
+/// Whether every file the commit changed, compared to its first parent, matches `paths`
+fn only_touches(repo: &Repository, commit: &Commit, paths: &Pathspec) -> DynErrResult<bool> {
+    let parent_tree = match commit.parent(0) {
+        Ok(parent) => Some(parent.tree()?),
+        Err(_) => None,
+    };
+    let diff = repo.diff_tree_to_tree(parent_tree.as_ref(), Some(&commit.tree()?), None)?;
+    let mut touched = diff
+        .deltas()
+        .filter_map(|delta| delta.new_file().path().or_else(|| delta.old_file().path()))
+        .peekable();
+    // an empty commit touches nothing, it isn't a lock file update
+    if touched.peek().is_none() {
+        return Ok(false);
+    }
+    Ok(touched.all(|path| paths.matches_path(path, PathspecFlags::DEFAULT)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::NoiseFilter;
+    use crate::config::Exclude;
+    use git2::{Oid, Repository, Signature};
+    use std::{env, fs};
+
+    fn commit(repo: &Repository, author: &str, message: &str, files: &[&str]) -> Oid {
+        let mut tree = repo.treebuilder(None).unwrap();
+        for file in files {
+            let blob = repo.blob(message.as_bytes()).unwrap();
+            tree.insert(file, blob, 0o100644).unwrap();
+        }
+        let tree = repo.find_tree(tree.write().unwrap()).unwrap();
+        let email = format!("{}@example.com", author.to_lowercase());
+        let signature = Signature::now(author, &email).unwrap();
+        repo.commit(None, &signature, &signature, message, &tree, &[])
+            .unwrap()
+    }
+
+    #[test]
+    fn test_is_noise() {
+        let path = env::temp_dir().join(format!("gitretro-noise-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&path);
+        let repo = Repository::init(&path).unwrap();
+        let filter = NoiseFilter::new(&Exclude {
+            authors: vec![r"\[bot\]$".into()],
+            emails: vec!["CI@example.com".into()],
+            messages: vec![r"^chore\(release\)".into()],
+            paths: vec!["Cargo.lock".into()],
+        })
+        .unwrap();
+        let is_noise = |author, message, files: &[&str]| {
+            let commit = repo
+                .find_commit(commit(&repo, author, message, files))
+                .unwrap();
+            filter.is_noise(&repo, &commit).unwrap()
+        };
+        assert!(is_noise("dependabot[bot]", "bump serde", &["Cargo.toml"]));
+        assert!(is_noise("CI", "bump version", &["Cargo.toml"]));
+        assert!(is_noise("Alice", "chore(release): 1.2.0", &["Cargo.toml"]));
+        assert!(is_noise("Alice", "update deps", &["Cargo.lock"]));
+        assert!(!is_noise("Alice", "feat: csv", &["Cargo.lock", "csv.rs"]));
+        assert!(NoiseFilter::new(&Exclude {
+            messages: vec!["(".into()],
+            ..Exclude::default()
+        })
+        .is_err());
+        fs::remove_dir_all(path).unwrap();
+    }
+}
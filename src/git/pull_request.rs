@@ -0,0 +1,98 @@
+use super::RetroCommit;
+
+/// A pull request recognised from the merge commit GitHub or GitLab made for it
+#[derive(Debug)]
+pub struct PullRequest {
+    /// `#123` on GitHub, `!45` on GitLab
+    pub reference: String,
+    pub number: u64,
+    pub source_branch: String,
+    /// Title of the request, the merge commit's summary when the message has none
+    pub title: String,
+    /// Commits of the source branch the merge brought into the mainline
+    pub commits: Vec<RetroCommit>,
+}
+
+impl PullRequest {
+    /// `message` is the whole message of a merge commit, like
+    /// `Merge pull request #123 from owner/branch` followed by the title,
+    /// or GitLab's `Merge branch 'branch' into 'master'` ending with `See merge request group/repo!45`
+    pub fn parse(message: &str) -> Option<Self> {
+        let mut lines = message.lines();
+        let summary = lines.next()?.trim();
+        let rest: Vec<&str> = lines
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .collect();
+        let github = summary.strip_prefix("Merge pull request #");
+        let (reference, number, source_branch) = match github {
+            Some(github) => {
+                let (number, from) = github.split_at(github.find(' ')?);
+                let source = from.trim_start().strip_prefix("from ")?;
+                // `owner/branch`, the branch name may have slashes too
+                let branch = source.split_once('/').map_or(source, |(_, branch)| branch);
+                ("#", number.parse().ok()?, branch)
+            }
+            None => {
+                let branch = summary.strip_prefix("Merge branch '")?;
+                let branch = &branch[..branch.find('\'')?];
+                let request = rest
+                    .iter()
+                    .find_map(|line| line.strip_prefix("See merge request "))?;
+                let number = &request[request.rfind('!')? + 1..];
+                ("!", number.parse().ok()?, branch)
+            }
+        };
+        let title = rest
+            .iter()
+            .find(|line| !line.starts_with("See merge request "))
+            .unwrap_or(&summary);
+        Some(Self {
+            reference: format!("{}{}", reference, number),
+            number,
+            source_branch: source_branch.into(),
+            title: title.to_string(),
+            commits: vec![],
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PullRequest;
+
+    #[test]
+    fn test_github_merge() {
+        let pull_request = PullRequest::parse(
+            "Merge pull request #123 from alice/feature/PAY-9-payouts\n\nfeat: PAY-9 payouts",
+        )
+        .unwrap();
+        assert_eq!(pull_request.reference, "#123");
+        assert_eq!(pull_request.number, 123);
+        assert_eq!(pull_request.source_branch, "feature/PAY-9-payouts");
+        assert_eq!(pull_request.title, "feat: PAY-9 payouts");
+    }
+
+    #[test]
+    fn test_gitlab_merge() {
+        let pull_request = PullRequest::parse(
+            "Merge branch 'fees' into 'master'\n\nfix: fees\n\nSee merge request team/pay!45",
+        )
+        .unwrap();
+        assert_eq!(pull_request.reference, "!45");
+        assert_eq!(pull_request.source_branch, "fees");
+        assert_eq!(pull_request.title, "fix: fees");
+        let untitled = PullRequest::parse(
+            "Merge branch 'fees' into 'master'\n\nSee merge request team/pay!45",
+        )
+        .unwrap();
+        assert_eq!(untitled.title, "Merge branch 'fees' into 'master'");
+    }
+
+    #[test]
+    fn test_other_merges() {
+        assert!(PullRequest::parse("Merge branch 'master' into fees").is_none());
+        assert!(PullRequest::parse("Merge remote-tracking branch 'origin/master'").is_none());
+        assert!(PullRequest::parse("feat: not a merge").is_none());
+    }
+}
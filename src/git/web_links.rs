@@ -0,0 +1,160 @@
+const COMMIT: &str = "{commit}";
+const BRANCH: &str = "{branch}";
+const NUMBER: &str = "{number}";
+const TAG: &str = "{tag}";
+
+/// URL templates pointing to the web page of a commit, a branch, a pull request or a tag,
+/// `{commit}`, `{branch}`, `{number}` and `{tag}` are replaced with the commit id, branch name,
+/// pull request number and tag name. An empty template makes no link
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct WebLinks {
+    pub commit_template: String,
+    pub branch_template: String,
+    pub pull_request_template: String,
+    pub tag_template: String,
+}
+
+impl WebLinks {
+    /// Guesses the hosting provider from a remote url like
+    /// `git@github.com:owner/repo.git` or `https://gitlab.com/group/repo.git`
+    pub fn from_remote_url(url: &str) -> Option<WebLinks> {
+        let base = web_base(url)?;
+        let host = base.trim_start_matches("https://");
+        let (commit, branch, pull_request, tag) = if host.contains("github") {
+            ("commit", "tree", "pull", "tree")
+        } else if host.contains("gitlab") {
+            ("-/commit", "-/tree", "-/merge_requests", "-/tags")
+        } else if host.contains("bitbucket") {
+            ("commits", "branch", "pull-requests", "commits/tag")
+        } else if host.contains("gitea") || host.contains("codeberg") {
+            ("commit", "src/branch", "pulls", "src/tag")
+        } else {
+            return None;
+        };
+        Some(WebLinks {
+            commit_template: format!("{}/{}/{}", base, commit, COMMIT),
+            branch_template: format!("{}/{}/{}", base, branch, BRANCH),
+            pull_request_template: format!("{}/{}/{}", base, pull_request, NUMBER),
+            tag_template: format!("{}/{}/{}", base, tag, TAG),
+        })
+    }
+
+    pub fn commit_url(&self, commit_id: &str) -> Option<String> {
+        fill(&self.commit_template, COMMIT, commit_id)
+    }
+
+    pub fn branch_url(&self, branch: &str) -> Option<String> {
+        fill(&self.branch_template, BRANCH, branch)
+    }
+
+    pub fn pull_request_url(&self, number: u64) -> Option<String> {
+        fill(&self.pull_request_template, NUMBER, &number.to_string())
+    }
+
+    pub fn tag_url(&self, tag: &str) -> Option<String> {
+        fill(&self.tag_template, TAG, tag)
+    }
+}
+
+fn fill(template: &str, placeholder: &str, value: &str) -> Option<String> {
+    if template.is_empty() {
+        None
+    } else {
+        Some(template.replace(placeholder, value))
+    }
+}
+
+/// `https://host/owner/repo` out of any of the url forms git accepts for a remote
+fn web_base(url: &str) -> Option<String> {
+    let url = url.trim().trim_end_matches('/').trim_end_matches(".git");
+    let (host, path) = match url.find("://") {
+        Some(index) => {
+            let scheme = &url[..index];
+            let rest = &url[index + 3..];
+            let (authority, path) = rest.split_at(rest.find('/')?);
+            let host = authority.rsplit('@').next()?;
+            // a ssh port has nothing to do with the web server
+            let host = match scheme {
+                "http" | "https" => host,
+                _ => host.split(':').next()?,
+            };
+            (host, path.trim_start_matches('/'))
+        }
+        None => {
+            // scp-like syntax `user@host:path`
+            let (authority, path) = url.split_at(url.find(':')?);
+            let host = authority.rsplit('@').next()?;
+            (host, path.trim_start_matches(':').trim_start_matches('/'))
+        }
+    };
+    if host.is_empty() || path.is_empty() {
+        None
+    } else {
+        Some(format!("https://{}/{}", host, path))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::WebLinks;
+
+    #[test]
+    fn test_github_scp_url() {
+        let links = WebLinks::from_remote_url("git@github.com:BProg/git-retro-slack.git").unwrap();
+        assert_eq!(
+            links.commit_url("abc123").unwrap(),
+            "https://github.com/BProg/git-retro-slack/commit/abc123"
+        );
+        assert_eq!(
+            links.branch_url("feature").unwrap(),
+            "https://github.com/BProg/git-retro-slack/tree/feature"
+        );
+    }
+
+    #[test]
+    fn test_gitlab_ssh_url_with_port() {
+        let links =
+            WebLinks::from_remote_url("ssh://git@gitlab.example.com:2222/group/sub/repo.git")
+                .unwrap();
+        assert_eq!(
+            links.commit_url("abc123").unwrap(),
+            "https://gitlab.example.com/group/sub/repo/-/commit/abc123"
+        );
+        assert_eq!(
+            links.pull_request_url(45).unwrap(),
+            "https://gitlab.example.com/group/sub/repo/-/merge_requests/45"
+        );
+    }
+
+    #[test]
+    fn test_https_urls() {
+        let bitbucket =
+            WebLinks::from_remote_url("https://user@bitbucket.org/team/repo.git").unwrap();
+        assert_eq!(
+            bitbucket.branch_url("fix").unwrap(),
+            "https://bitbucket.org/team/repo/branch/fix"
+        );
+        let gitea = WebLinks::from_remote_url("https://gitea.local:3000/me/repo").unwrap();
+        assert_eq!(
+            gitea.branch_url("fix").unwrap(),
+            "https://gitea.local:3000/me/repo/src/branch/fix"
+        );
+        assert_eq!(
+            gitea.tag_url("v1.0.0").unwrap(),
+            "https://gitea.local:3000/me/repo/src/tag/v1.0.0"
+        );
+        assert_eq!(
+            bitbucket.tag_url("v1.0.0").unwrap(),
+            "https://bitbucket.org/team/repo/commits/tag/v1.0.0"
+        );
+    }
+
+    #[test]
+    fn test_unknown_host() {
+        assert_eq!(
+            WebLinks::from_remote_url("git@example.com:me/repo.git"),
+            None
+        );
+        assert_eq!(WebLinks::from_remote_url("/local/path/repo"), None);
+    }
+}
@@ -0,0 +1,167 @@
+use crate::{email::EmailMessage, fs::get_history_file, slack::SlackThread, DynErrResult};
+use chrono::Local;
+use confy::{load_path, store_path};
+use serde::{Deserialize, Serialize};
+use std::{error::Error, fmt::Display, fs, path::Path};
+
+#[derive(Debug)]
+pub enum HistoryError {
+    NotFound(String),
+    /// Ids are file names in the history directory, a path could point out of it
+    InvalidId(String),
+}
+
+impl Error for HistoryError {}
+
+impl Display for HistoryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HistoryError::NotFound(id) => write!(f, "No report with id {} in history", id),
+            HistoryError::InvalidId(id) => {
+                write!(f, "{} is not a report id, it can't be a path", id)
+            }
+        }
+    }
+}
+
+/// A report exactly as it was rendered for delivery, kept so it can be re-sent
+#[derive(Serialize, Deserialize, Default)]
+pub struct HistoryEntry {
+    pub id: String,
+    pub created_at: String,
+    pub payload: String,
+    /// The payload cut in several web hook messages when it's too long for one
+    #[serde(default)]
+    pub parts: Vec<String>,
+    /// Name of the scope the report is about, it's re-sent to the scope's channel
+    #[serde(default)]
+    pub scope: Option<String>,
+    // toml wants the tables after the plain values, keep them last
+    #[serde(default)]
+    pub thread: Option<SlackThread>,
+    #[serde(default)]
+    pub email: Option<EmailMessage>,
+}
+
+impl HistoryEntry {
+    pub fn new(payload: impl Into<String>) -> Self {
+        let now = Local::now();
+        Self {
+            id: now.format("%Y%m%d%H%M%S").to_string(),
+            created_at: now.to_rfc3339(),
+            payload: payload.into(),
+            parts: vec![],
+            scope: None,
+            thread: None,
+            email: None,
+        }
+    }
+
+    /// Reports of several scopes are made in the same second, the scope keeps their ids apart.
+    /// Slashes and dots of the scope's name become dashes, the id is a file name which gets
+    /// its extension after the last dot
+    pub fn scoped(self, scope: &str) -> Self {
+        let name = scope.replace(['/', '\\', '.'], "-");
+        Self {
+            id: format!("{}-{}", self.id, name),
+            scope: Some(scope.into()),
+            ..self
+        }
+    }
+
+    pub fn load(id: &str) -> DynErrResult<HistoryEntry> {
+        check_id(id)?;
+        Self::load_from(id, &get_history_file(id)?)
+    }
+
+    fn load_from(id: &str, file: &Path) -> DynErrResult<HistoryEntry> {
+        if !file.exists() {
+            return Err(HistoryError::NotFound(id.into()).into());
+        }
+        load_path(file).map_err(Box::from)
+    }
+
+    pub fn store(&self) -> DynErrResult<()> {
+        check_id(&self.id)?;
+        self.store_to(&get_history_file(&self.id)?)
+    }
+
+    /// The history directory is created by the first report
+    fn store_to(&self, file: &Path) -> DynErrResult<()> {
+        if let Some(dir) = file.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        store_path(file, self).map_err(Box::from)
+    }
+}
+
+fn check_id(id: &str) -> Result<(), HistoryError> {
+    if id.is_empty() || id == "." || id == ".." || id.contains(['/', '\\']) {
+        return Err(HistoryError::InvalidId(id.into()));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::HistoryEntry;
+    use crate::{email::EmailMessage, slack::SlackThread};
+    use std::{env, fs};
+
+    #[test]
+    fn test_store_and_load() {
+        // like on a fresh install, the history directory doesn't exist yet
+        let dir = env::temp_dir().join(format!("gitretro-history-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let file = dir.join("history").join("report.toml");
+        let entry = HistoryEntry {
+            parts: vec!["first".into(), "second".into()],
+            thread: Some(SlackThread {
+                parent: "first".into(),
+                replies: vec!["second".into()],
+                attachment: None,
+            }),
+            email: Some(EmailMessage {
+                subject: "Team git-status".into(),
+                html: "<p>first</p>".into(),
+                text: "first".into(),
+            }),
+            ..HistoryEntry::new("first\nsecond").scoped("backend")
+        };
+        entry.store_to(&file).unwrap();
+        let loaded = HistoryEntry::load_from(&entry.id, &file).unwrap();
+        assert_eq!(loaded.id, entry.id);
+        assert_eq!(loaded.created_at, entry.created_at);
+        assert_eq!(loaded.payload, "first\nsecond");
+        assert_eq!(loaded.parts, entry.parts);
+        assert_eq!(loaded.scope.as_deref(), Some("backend"));
+        assert_eq!(loaded.thread.unwrap().replies, vec!["second".to_string()]);
+        assert_eq!(loaded.email.unwrap().subject, "Team git-status");
+        fs::remove_file(&file).unwrap();
+
+        let missing = HistoryEntry::load_from(&entry.id, &file).err().unwrap();
+        assert_eq!(
+            missing.to_string(),
+            format!("No report with id {} in history", entry.id)
+        );
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_invalid_id() {
+        for id in &["../config", "..", "a/b", r"..\config", ""] {
+            let error = HistoryEntry::load(id).err().unwrap();
+            assert_eq!(
+                error.to_string(),
+                format!("{} is not a report id, it can't be a path", id)
+            );
+        }
+        let entry = HistoryEntry::new("report").scoped("services/api");
+        assert!(entry.id.ends_with("-services-api"));
+        assert_eq!(entry.scope.as_deref(), Some("services/api"));
+        let app = HistoryEntry::new("report").scoped("web.app");
+        let api = HistoryEntry::new("report").scoped("web.api");
+        assert!(app.id.ends_with("-web-app"));
+        assert_ne!(app.id, api.id);
+    }
+}
@@ -1,14 +1,22 @@
 mod cli;
 mod config;
+mod email;
 mod fs;
 mod git;
+mod history;
 mod launchd;
+mod report;
 mod slack;
+mod tracker;
 
-use cli::{configure, log, Command};
-use config::Config;
-use git::{search_interval::SearchInterval, RepoAnalyzer};
-use reqwest::blocking;
+use cli::{configure, log, Command, RunOptions};
+use config::{Config, Scope, SlackOversized};
+use email::EmailMessage;
+use git::{search_interval::SearchInterval, AnalyzerOptions, RepoAnalyzer};
+use history::HistoryEntry;
+use report::{export, Format, MessageIngredients};
+use slack::{api::SlackApi, Mentions};
+use tracker::IssueTracker;
 use std::*;
 
 pub const APP_NAME: &str = "git-retrospective";
@@ -17,15 +25,20 @@ pub type DynErrResult<T> = Result<T, Box<dyn error::Error>>;
 fn main() {
     match Command::parse_args() {
         Command::Help => {
-            log::message(Command::Help.help());
+            log::output(Command::Help.help());
         },
         Command::Config => {
             if let Err(e) = configure().and_then(|cfg| cfg.store()) {
                 log::error(e.to_string());
             }
         }
-        Command::Run => {
-            if let Err(e) = run() {
+        Command::Run(options) => {
+            if let Err(e) = run(&options) {
+                log::error(e.to_string());
+            }
+        }
+        Command::Resend(id) => {
+            if let Err(e) = resend(&id) {
                 log::error(e.to_string());
             }
         }
@@ -48,13 +61,46 @@ fn main() {
     };
 }
 
-fn run() -> DynErrResult<blocking::Response> {
+fn run(options: &RunOptions) -> DynErrResult<()> {
     let app_config = Config::load()?;
     log::multiple(vec![
         log::Style::Message("Config: "),
         log::Style::Important(&app_config.to_string()),
     ]);
-    let repo = RepoAnalyzer::new(&app_config.repo_path)?;
+    let scopes: Vec<(&String, &Scope)> = match &options.scope {
+        Some(name) => match app_config.scopes.get_key_value(name) {
+            Some(scope) => vec![scope],
+            None => return Err(format!("No scope named {} in the config", name).into()),
+        },
+        None => app_config.scopes.iter().collect(),
+    };
+    // an export is a single document on stdout, scripts couldn't parse several
+    if options.format != Format::Slack && scopes.len() > 1 {
+        return Err("Several scopes are configured, export one of them with --scope <name>".into());
+    }
+    let mut repo = match &app_config.repo_url {
+        Some(url) => RepoAnalyzer::mirror(url, AnalyzerOptions::from(&app_config))?,
+        None => {
+            let mut repo = RepoAnalyzer::new(&app_config.repo_path)?;
+            repo.set_options(AnalyzerOptions::from(&app_config))?;
+            repo
+        }
+    };
+    let mut notes = vec![];
+    if options.no_fetch || app_config.offline {
+        log::message("Not fetching, the local refs are analysed as they are");
+    } else if let Err(e) = repo.fetch() {
+        log::error(format!("Fetch failed, going on with the local refs: {}", e));
+        notes.push("Fetching the remote failed, the data may be stale".into());
+    }
+    // refs are resolved after the fetch, the release may have just been tagged
+    let refs = match (&options.from_ref, &options.to_ref) {
+        (Some(from_ref), Some(to_ref)) => {
+            repo.set_refs(from_ref, to_ref)?;
+            Some((from_ref.clone(), to_ref.clone()))
+        }
+        _ => None,
+    };
     let SearchInterval { from, to } = repo.interval;
     log::multiple(vec![
         log::Style::Message("Searching logs from: "),
@@ -62,13 +108,139 @@ fn run() -> DynErrResult<blocking::Response> {
         log::Style::Message(" to "),
         log::Style::Important(&to.to_string()),
     ]);
+    if scopes.is_empty() {
+        return send_report(&app_config, &repo, options, notes, refs, None);
+    }
+    for (name, scope) in scopes {
+        log::multiple(vec![
+            log::Style::Message("Reporting on scope "),
+            log::Style::Important(name),
+        ]);
+        repo.set_options(AnalyzerOptions {
+            paths: scope.paths.clone(),
+            ..AnalyzerOptions::from(&app_config)
+        })?;
+        let scope_config = app_config.scoped(scope);
+        let notes = notes.clone();
+        send_report(&scope_config, &repo, options, notes, refs.clone(), Some(name))?;
+    }
+    Ok(())
+}
+
+/// Makes the report of the analysed repository, or of one of its scopes, and sends it
+fn send_report(
+    app_config: &Config,
+    repo: &RepoAnalyzer,
+    options: &RunOptions,
+    notes: Vec<String>,
+    refs: Option<(String, String)>,
+    scope: Option<&str>,
+) -> DynErrResult<()> {
     let commits = repo.get_commits()?;
-    let branches = repo.get_in_progress()?;
-    let message = slack::MessageIngredients {
+    // release notes are about what was merged, not about the work in progress
+    let (branches, stale_branches) = if refs.is_some() {
+        (vec![], vec![])
+    } else {
+        (
+            repo.get_in_progress()?,
+            repo.get_stale(app_config.stale_after())?,
+        )
+    };
+    let releases = repo.get_releases()?;
+    let message = MessageIngredients {
         branches,
+        stale_branches,
+        releases,
         commits,
-        interval: repo.interval
+        interval: repo.interval,
+        refs,
+        scope: scope.map(String::from),
+        notes,
+        links: repo.get_web_links(),
+        remote_links: repo.get_remote_web_links(),
+        tracker: IssueTracker::from_config(app_config)?,
+        stale_after: app_config.stale_after(),
+        mentions: Mentions::from_config(app_config),
     };
-    message.send_to_slack(app_config.slack_web_hook, message.format_slack())
+    let report = message.report();
+    if options.format != Format::Slack {
+        log::output(export(&report, options.format)?);
+        return Ok(());
+    }
+    let template = app_config.load_template()?;
+    let template = template.as_deref();
+    let max_length = app_config
+        .slack_max_length
+        .unwrap_or(slack::MAX_MESSAGE_LENGTH);
+    let mut entry = HistoryEntry::new(slack::format_slack(&report, template)?);
+    if let Some(scope) = scope {
+        entry = entry.scoped(scope);
+    }
+    let oversized = slack::length(&entry.payload) > max_length;
+    if SlackApi::from_config(app_config).is_some() {
+        entry.thread = Some(
+            if oversized && app_config.slack_oversized == SlackOversized::File {
+                slack::format_slack_collapsed(&report, template)?
+            } else {
+                slack::format_slack_thread(&report, template, max_length)?
+            },
+        );
+    } else if oversized {
+        if app_config.slack_oversized == SlackOversized::File {
+            log::message("Attaching files needs the slack bot token, splitting the report instead");
+        }
+        entry.parts = slack::format_slack_parts(&report, template, max_length)?;
+    }
+    if app_config.smtp.is_some() {
+        entry.email = Some(EmailMessage::new(&report)?);
+    }
+    entry.store()?;
+    log::multiple(vec![
+        log::Style::Message("Report stored in history as "),
+        log::Style::Important(&entry.id),
+    ]);
+    deliver(app_config, &entry)
+}
+
+fn resend(id: &str) -> DynErrResult<()> {
+    let app_config = Config::load()?;
+    let entry = HistoryEntry::load(id)?;
+    log::multiple(vec![
+        log::Style::Message("Resending report from "),
+        log::Style::Important(&entry.created_at),
+    ]);
+    // a scope's report goes back to the scope's channel
+    let scope = entry
+        .scope
+        .as_ref()
+        .and_then(|scope| app_config.scopes.get(scope));
+    match scope {
+        Some(scope) => deliver(&app_config.scoped(scope), &entry),
+        None => deliver(&app_config, &entry),
+    }
+}
+
+/// Sends the report to every configured destination
+fn deliver(app_config: &Config, entry: &HistoryEntry) -> DynErrResult<()> {
+    if let Some(api) = SlackApi::from_config(app_config) {
+        match &entry.thread {
+            Some(thread) => api.post_thread(thread)?,
+            None => api.post_message(&entry.payload, None).map(|_| ())?,
+        }
+    } else if !app_config.slack_web_hook.is_empty() {
+        let parts = if entry.parts.is_empty() {
+            slice::from_ref(&entry.payload)
+        } else {
+            entry.parts.as_slice()
+        };
+        for part in parts {
+            let response = slack::send_to_slack(&app_config.slack_web_hook, part)?;
+            log::message(format!("{:?}", &response));
+        }
+    }
+    if let (Some(smtp), Some(message)) = (&app_config.smtp, &entry.email) {
+        email::send_email(smtp, message)?;
+    }
+    Ok(())
 }
 
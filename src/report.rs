@@ -0,0 +1,737 @@
+use crate::{
+    git::{
+        search_interval::SearchInterval, BranchStatus, Category, Contributor, DiffStats,
+        PullRequest, Release, RetroCommit, WebLinks, WorkingBranch,
+    },
+    slack::Mentions,
+    tracker::IssueTracker,
+};
+use chrono::Duration;
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+
+mod export;
+mod json;
+mod template;
+
+pub use export::{export, Format};
+pub use template::render;
+
+pub struct MessageIngredients<C, B>
+where
+    C: AsRef<[RetroCommit]>,
+    B: AsRef<[WorkingBranch]>,
+{
+    pub commits: C,
+    pub branches: B,
+    /// Branches without commits for `stale_after`, usually older than the interval
+    pub stale_branches: B,
+    /// Tags of the interval, oldest first
+    pub releases: Vec<Release>,
+    pub interval: SearchInterval,
+    /// The tags or commits of release notes, shown instead of the interval's dates
+    pub refs: Option<(String, String)>,
+    /// Name of the part of the repository the report is about, see `config::Scope`
+    pub scope: Option<String>,
+    /// Caveats about the data, shown under the header
+    pub notes: Vec<String>,
+    pub links: Option<WebLinks>,
+    /// Links of the other remotes by name, their branches aren't on the mainline's host
+    pub remote_links: BTreeMap<String, WebLinks>,
+    pub tracker: Option<IssueTracker>,
+    /// Branches without commits for longer than this are stale
+    pub stale_after: Duration,
+    pub mentions: Option<Mentions>,
+}
+
+/// Everything a template can show, already grouped and linked
+#[derive(Serialize, Clone)]
+pub struct Report {
+    /// False when the report is a follow-up of another message, like a thread reply
+    pub header: bool,
+    pub scope: Option<String>,
+    pub from: String,
+    pub to: String,
+    pub notes: Vec<String>,
+    pub stats: Option<DiffStats>,
+    /// False when nobody writes conventional commits, the sections would be noise
+    pub categorized: bool,
+    pub tickets: Vec<ReportTicket>,
+    pub releases: Vec<ReportRelease>,
+    pub stale_branches: Vec<ReportBranch>,
+    pub authors: Vec<ReportAuthor>,
+}
+
+#[derive(Serialize, Clone)]
+pub struct ReportCommit {
+    pub id: String,
+    pub short_id: String,
+    pub url: Option<String>,
+    pub author: String,
+    pub email: String,
+    pub message: String,
+    /// Whole commit message, including the summary
+    pub body: String,
+    pub authored_at: String,
+    pub committed_at: String,
+    pub merge: bool,
+    /// Title of the commit's section, like `Features`
+    pub category: &'static str,
+    /// The json export names the category by its key rather than its title
+    #[serde(skip)]
+    pub kind: Category,
+    pub pull_request: Option<ReportPullRequest>,
+}
+
+/// The commits of a pull request are only listed here, under its merge commit
+#[derive(Serialize, Clone)]
+pub struct ReportPullRequest {
+    pub reference: String,
+    pub url: Option<String>,
+    pub source_branch: String,
+    pub commits: Vec<ReportCommit>,
+}
+
+#[derive(Serialize, Clone)]
+pub struct ReportBranch {
+    pub name: String,
+    pub url: Option<String>,
+    pub author: String,
+    pub last_activity: String,
+    pub stale: bool,
+    pub status: Option<BranchStatus>,
+    /// Empty when nobody committed to the branch during the interval
+    pub contributors: Vec<Contributor>,
+    pub remote: Option<String>,
+    pub local: bool,
+    pub unpushed: Option<usize>,
+}
+
+#[derive(Serialize, Clone)]
+pub struct ReportRelease {
+    pub tag: String,
+    pub url: Option<String>,
+    pub tagged_at: String,
+    /// What the tag brought since the previous one
+    pub commits: Vec<ReportCommit>,
+}
+
+#[derive(Serialize, Clone)]
+pub struct ReportSection {
+    pub title: &'static str,
+    #[serde(skip)]
+    pub kind: Category,
+    pub commits: Vec<ReportCommit>,
+}
+
+#[derive(Serialize, Clone)]
+pub struct ReportAuthor {
+    pub name: String,
+    /// Slack user id, when the author should be notified
+    pub mention: Option<String>,
+    pub stats: Option<DiffStats>,
+    pub sections: Vec<ReportSection>,
+    pub branches: Vec<ReportBranch>,
+}
+
+#[derive(Serialize, Clone)]
+pub struct ReportTicket {
+    pub key: String,
+    pub url: Option<String>,
+    pub commits: Vec<ReportCommit>,
+    pub branches: Vec<ReportBranch>,
+}
+
+#[derive(Default)]
+struct AuthorJobs {
+    emails: BTreeSet<String>,
+    done: BTreeMap<Category, Vec<ReportCommit>>,
+    in_progress: Vec<ReportBranch>,
+    stats: Option<DiffStats>,
+}
+
+#[derive(Default)]
+struct TicketJobs {
+    done: Vec<ReportCommit>,
+    in_progress: Vec<ReportBranch>,
+}
+
+impl<C, B> MessageIngredients<C, B>
+where
+    C: AsRef<[RetroCommit]>,
+    B: AsRef<[WorkingBranch]>,
+{
+    pub fn report(&self) -> Report {
+        let mut author_jobs_map: BTreeMap<String, AuthorJobs> = BTreeMap::new();
+        let mut ticket_jobs_map: BTreeMap<String, TicketJobs> = BTreeMap::new();
+        let mut repo_stats: Option<DiffStats> = None;
+        let mut commits = without_pulled(self.commits.as_ref());
+        commits.sort_by_key(|commit| commit.committed_at);
+        for commit in commits {
+            let (author, email) = commit.owner();
+            let jobs = author_jobs_map.entry(author.into()).or_default();
+            jobs.emails.insert(email.into());
+            let category = commit.category();
+            let done = self.report_commit(commit);
+            let text = match &commit.pull_request {
+                Some(pull_request) => {
+                    format!("{}\n{}", pull_request.title, pull_request.source_branch)
+                }
+                None => commit.message.clone(),
+            };
+            for key in self.issue_keys(&text) {
+                ticket_jobs_map
+                    .entry(key)
+                    .or_default()
+                    .done
+                    .push(done.clone());
+            }
+            jobs.done.entry(category).or_default().push(done);
+            if let Some(stats) = commit.stats {
+                *jobs.stats.get_or_insert_with(DiffStats::default) += stats;
+                *repo_stats.get_or_insert_with(DiffStats::default) += stats;
+            }
+        }
+        for branch in self.branches.as_ref() {
+            let in_progress = self.report_branch(branch);
+            for key in self.issue_keys(branch.short_name()) {
+                let tickets = ticket_jobs_map.entry(key).or_default();
+                tickets.in_progress.push(in_progress.clone());
+            }
+            // the branch shows up for everyone who worked on it, and for its owner
+            let mut owners: Vec<(&String, &String)> = branch
+                .contributors
+                .iter()
+                .map(|contributor| (&contributor.name, &contributor.email))
+                .collect();
+            if !owners.iter().any(|(name, _)| **name == branch.author) {
+                owners.push((&branch.author, &branch.email));
+            }
+            for (name, email) in owners {
+                let jobs = author_jobs_map.entry(name.clone()).or_default();
+                // owners of a remote have no email, see `AnalyzerOptions::remote_owners`
+                if !email.is_empty() {
+                    jobs.emails.insert(email.clone());
+                }
+                jobs.in_progress.push(in_progress.clone());
+            }
+        }
+
+        let stale_branches: Vec<ReportBranch> = self
+            .stale_branches
+            .as_ref()
+            .iter()
+            .map(|branch| self.report_branch(branch))
+            .collect();
+
+        Report {
+            header: true,
+            scope: self.scope.clone(),
+            from: match &self.refs {
+                Some((from, _)) => from.clone(),
+                None => self.interval.from.to_string(),
+            },
+            to: match &self.refs {
+                Some((_, to)) => to.clone(),
+                None => self.interval.to.to_string(),
+            },
+            notes: self.notes.clone(),
+            stats: repo_stats,
+            categorized: self
+                .commits
+                .as_ref()
+                .iter()
+                .any(|commit| commit.category() != Category::Other),
+            releases: self
+                .releases
+                .iter()
+                .map(|release| ReportRelease {
+                    tag: release.tag.clone(),
+                    // hosting providers show tags on the same pages as branches
+                    url: self
+                        .links
+                        .as_ref()
+                        .and_then(|links| links.tag_url(&release.tag)),
+                    tagged_at: release.tagged_at.to_string(),
+                    commits: without_pulled(&release.commits)
+                        .into_iter()
+                        .map(|commit| self.report_commit(commit))
+                        .collect(),
+                })
+                .collect(),
+            tickets: ticket_jobs_map
+                .into_iter()
+                .map(|(key, jobs)| ReportTicket {
+                    url: self.tracker.as_ref().and_then(|tracker| tracker.url(&key)),
+                    key,
+                    commits: jobs.done,
+                    branches: jobs.in_progress,
+                })
+                .collect(),
+            authors: author_jobs_map
+                .into_iter()
+                .map(|(name, jobs)| ReportAuthor {
+                    mention: self.mentions.as_ref().and_then(|mentions| {
+                        let has_stale = jobs.in_progress.iter().any(|branch| branch.stale)
+                            || stale_branches.iter().any(|branch| branch.author == name);
+                        mentions.user_id(&name, &jobs.emails, has_stale)
+                    }),
+                    name,
+                    stats: jobs.stats,
+                    sections: jobs
+                        .done
+                        .into_iter()
+                        .map(|(category, commits)| ReportSection {
+                            title: category.title(),
+                            kind: category,
+                            commits,
+                        })
+                        .collect(),
+                    branches: jobs.in_progress,
+                })
+                .collect(),
+            stale_branches,
+        }
+    }
+
+    fn report_commit(&self, commit: &RetroCommit) -> ReportCommit {
+        let pull_request = commit
+            .pull_request
+            .as_ref()
+            .map(|pull_request| self.report_pull_request(pull_request));
+        let (author, email) = commit.owner();
+        let category = commit.category();
+        ReportCommit {
+            id: commit.id.clone(),
+            short_id: commit.short_id.clone(),
+            url: self
+                .links
+                .as_ref()
+                .and_then(|links| links.commit_url(&commit.id)),
+            author: author.into(),
+            email: email.into(),
+            message: match &commit.pull_request {
+                Some(pull_request) => pull_request.title.clone(),
+                None => commit.message.clone(),
+            },
+            body: commit.body.clone(),
+            authored_at: commit.authored_at.to_string(),
+            committed_at: commit.committed_at.to_string(),
+            merge: commit.is_merge(),
+            category: category.title(),
+            kind: category,
+            pull_request,
+        }
+    }
+
+    fn report_pull_request(&self, pull_request: &PullRequest) -> ReportPullRequest {
+        ReportPullRequest {
+            reference: pull_request.reference.clone(),
+            url: self
+                .links
+                .as_ref()
+                .and_then(|links| links.pull_request_url(pull_request.number)),
+            source_branch: pull_request.source_branch.clone(),
+            commits: pull_request
+                .commits
+                .iter()
+                .map(|commit| self.report_commit(commit))
+                .collect(),
+        }
+    }
+
+    fn report_branch(&self, branch: &WorkingBranch) -> ReportBranch {
+        // a branch which was never pushed has no page on the hosting provider
+        let pushed = !branch.local || branch.unpushed.is_some();
+        let links = match &branch.remote {
+            Some(remote) => self.remote_links.get(remote).or(self.links.as_ref()),
+            None => self.links.as_ref(),
+        };
+        ReportBranch {
+            name: branch.name.clone(),
+            url: links
+                .filter(|_| pushed)
+                .and_then(|links| links.branch_url(branch.short_name())),
+            author: branch.author.clone(),
+            last_activity: branch.last_activity.to_string(),
+            stale: branch.last_activity < self.interval.to - self.stale_after,
+            status: branch.status,
+            contributors: branch.contributors.clone(),
+            remote: branch.remote.clone(),
+            local: branch.local,
+            unpushed: branch.unpushed,
+        }
+    }
+
+    fn issue_keys(&self, text: &str) -> Vec<String> {
+        match &self.tracker {
+            Some(tracker) => tracker.keys(text),
+            None => vec![],
+        }
+    }
+}
+
+/// The commits which aren't part of a pull request, and the pull requests' merge commits
+fn without_pulled(commits: &[RetroCommit]) -> Vec<&RetroCommit> {
+    let pulled: BTreeSet<&str> = commits
+        .iter()
+        .filter_map(|commit| commit.pull_request.as_ref())
+        .flat_map(|pull_request| &pull_request.commits)
+        .map(|commit| commit.id.as_str())
+        .collect();
+    commits
+        .iter()
+        .filter(|commit| !pulled.contains(commit.id.as_str()))
+        .collect()
+}
+
+impl Report {
+    /// Everything but the details of each author
+    pub fn summary(&self) -> Report {
+        Report {
+            authors: vec![],
+            ..self.clone()
+        }
+    }
+
+    /// One report per author, with only that author's details
+    pub fn author_details(&self) -> Vec<Report> {
+        self.authors
+            .iter()
+            .map(|author| Report {
+                header: false,
+                notes: vec![],
+                stats: None,
+                tickets: vec![],
+                releases: vec![],
+                stale_branches: vec![],
+                authors: vec![author.clone()],
+                ..self.clone()
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+pub mod fixtures {
+    use super::MessageIngredients;
+    use crate::{
+        git::{
+            search_interval::SearchInterval, BranchStatus, Contributor, DiffStats, PullRequest,
+            Release, RetroCommit, WebLinks, WorkingBranch,
+        },
+        tracker::IssueTracker,
+    };
+    use chrono::{Duration, NaiveDate, NaiveDateTime};
+    use std::collections::BTreeMap;
+
+    fn day(hour: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd(2020, 5, 24).and_hms(hour, 0, 0)
+    }
+
+    fn commit(id: &str, author: &str, message: &str, hour: u32) -> RetroCommit {
+        RetroCommit {
+            id: id.repeat(40),
+            short_id: id.repeat(7),
+            author: author.into(),
+            email: format!("{}@example.com", author.to_lowercase()),
+            message: message.into(),
+            body: message.into(),
+            authored_at: day(hour),
+            committed_at: day(hour),
+            parent_count: 1,
+            pull_request: None,
+            stats: Some(DiffStats {
+                insertions: 10,
+                deletions: 2,
+                files_changed: 1,
+            }),
+        }
+    }
+
+    /// Bob merged Carol's pull request
+    fn merge() -> RetroCommit {
+        RetroCommit {
+            parent_count: 2,
+            pull_request: Some(PullRequest {
+                reference: "#12".into(),
+                number: 12,
+                source_branch: "csv-exports".into(),
+                title: "feat: csv exports".into(),
+                commits: vec![
+                    commit("f", "Carol", "test: csv writer", 9),
+                    commit("e", "Carol", "add csv writer", 8),
+                ],
+            }),
+            ..commit("d", "Bob", "Merge pull request #12 from carol/csv-exports", 14)
+        }
+    }
+
+    pub fn ingredients() -> MessageIngredients<Vec<RetroCommit>, Vec<WorkingBranch>> {
+        MessageIngredients {
+            commits: vec![
+                merge(),
+                commit("f", "Carol", "test: csv writer", 9),
+                commit("b", "Bob", "fix: PAY-7 rounding <cents>", 12),
+                commit("a", "Alice", "feat(api): PAY-7 refunds", 10),
+                commit("c", "Alice", "chore: bump deps & tidy", 11),
+            ],
+            branches: vec![
+                WorkingBranch {
+                    author: "Bob".into(),
+                    email: "bob@example.com".into(),
+                    name: "origin/PAY-9-payouts".into(),
+                    last_activity: day(9),
+                    status: Some(BranchStatus {
+                        ahead: 2,
+                        behind: 1,
+                        conflicts: true,
+                    }),
+                    contributors: vec![
+                        Contributor {
+                            name: "Bob".into(),
+                            email: "bob@example.com".into(),
+                            commits: 3,
+                        },
+                        Contributor {
+                            name: "Alice".into(),
+                            email: "alice@example.com".into(),
+                            commits: 1,
+                        },
+                    ],
+                    remote: Some("origin".into()),
+                    local: false,
+                    unpushed: None,
+                },
+                WorkingBranch {
+                    author: "Bob".into(),
+                    email: "bob@example.com".into(),
+                    name: "PAY-9-fees".into(),
+                    last_activity: day(13),
+                    status: Some(BranchStatus {
+                        ahead: 1,
+                        behind: 0,
+                        conflicts: false,
+                    }),
+                    contributors: vec![],
+                    remote: None,
+                    local: true,
+                    unpushed: None,
+                },
+            ],
+            stale_branches: vec![WorkingBranch {
+                author: "Alice".into(),
+                email: "alice@example.com".into(),
+                name: "origin/old-experiment".into(),
+                last_activity: NaiveDate::from_ymd(2020, 5, 1).and_hms(15, 30, 0),
+                status: Some(BranchStatus {
+                    ahead: 1,
+                    behind: 40,
+                    conflicts: true,
+                }),
+                contributors: vec![],
+                remote: Some("origin".into()),
+                local: false,
+                unpushed: None,
+            }],
+            releases: vec![Release {
+                tag: "v1.4.0".into(),
+                tagged_at: day(13),
+                commits: vec![
+                    commit("b", "Bob", "fix: PAY-7 rounding <cents>", 12),
+                    commit("a", "Alice", "feat(api): PAY-7 refunds", 10),
+                ],
+            }],
+            interval: SearchInterval::starting(day(22), Duration::days(1)),
+            refs: None,
+            scope: None,
+            notes: vec![],
+            links: WebLinks::from_remote_url("git@github.com:team/pay.git"),
+            remote_links: BTreeMap::new(),
+            tracker: IssueTracker::new(
+                r"\bPAY-\d+\b",
+                Some("https://jira.example.com/browse/{issue}".into()),
+            )
+            .ok(),
+            stale_after: Duration::days(7),
+            mentions: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::fixtures;
+    use crate::{
+        config::SlackMentions,
+        git::{WebLinks, WorkingBranch},
+        slack::Mentions,
+    };
+    use chrono::{Duration, NaiveDate};
+
+    fn mentions(mode: SlackMentions) -> Option<Mentions> {
+        let users = vec![
+            ("alice@example.com".to_string(), "UALICE".to_string()),
+            ("bob@example.com".to_string(), "UBOB".to_string()),
+        ];
+        Some(Mentions::new(users.into_iter().collect(), mode))
+    }
+
+    #[test]
+    fn test_mentions() {
+        let mut ingredients = fixtures::ingredients();
+        ingredients.mentions = mentions(SlackMentions::All);
+        let report = ingredients.report();
+        assert_eq!(report.authors[0].mention.as_deref(), Some("UALICE"));
+        assert_eq!(report.authors[1].mention.as_deref(), Some("UBOB"));
+    }
+
+    #[test]
+    fn test_mentions_only_stale() {
+        let mut ingredients = fixtures::ingredients();
+        ingredients.mentions = mentions(SlackMentions::Stale);
+        ingredients.stale_branches = vec![];
+        ingredients.branches[0].contributors.clear();
+        assert!(ingredients
+            .report()
+            .authors
+            .iter()
+            .all(|author| author.mention.is_none()));
+
+        // Bob's branch has no commits since 9:00, the report ends at 22:00
+        ingredients.stale_after = Duration::hours(6);
+        let report = ingredients.report();
+        assert!(report.authors[0].mention.is_none());
+        assert_eq!(report.authors[1].mention.as_deref(), Some("UBOB"));
+    }
+
+    #[test]
+    fn test_branch_contributors() {
+        let report = fixtures::ingredients().report();
+        let names = |author: &super::ReportAuthor| -> Vec<String> {
+            author.branches.iter().map(|branch| branch.name.clone()).collect()
+        };
+        assert_eq!(names(&report.authors[0]), vec!["origin/PAY-9-payouts"]);
+        assert_eq!(
+            names(&report.authors[1]),
+            vec!["origin/PAY-9-payouts", "PAY-9-fees"]
+        );
+        assert_eq!(report.tickets[1].branches.len(), 2);
+    }
+
+    #[test]
+    fn test_fork_branch() {
+        let mut ingredients = fixtures::ingredients();
+        ingredients.remote_links.insert(
+            "alice".into(),
+            WebLinks::from_remote_url("git@github.com:alice/pay.git").unwrap(),
+        );
+        // the analyzer made Alice, who owns the remote, the author
+        ingredients.branches.push(WorkingBranch {
+            author: "Alice".into(),
+            email: "".into(),
+            name: "alice/PAY-12-csv".into(),
+            last_activity: NaiveDate::from_ymd(2020, 5, 24).and_hms(14, 0, 0),
+            status: None,
+            contributors: vec![],
+            remote: Some("alice".into()),
+            local: false,
+            unpushed: None,
+        });
+        let report = ingredients.report();
+        let ticket = report.tickets.iter().find(|ticket| ticket.key == "PAY-12");
+        let fork = &ticket.unwrap().branches[0];
+        assert_eq!(
+            fork.url.as_deref(),
+            Some("https://github.com/alice/pay/tree/PAY-12-csv")
+        );
+        assert!(report.authors[0]
+            .branches
+            .iter()
+            .any(|branch| branch.name == "alice/PAY-12-csv"));
+    }
+
+    #[test]
+    fn test_fork_branch_mention() {
+        let mut ingredients = fixtures::ingredients();
+        let users = vec![("carol@example.com".to_string(), "UCAROL".to_string())];
+        ingredients.mentions = Some(Mentions::new(
+            users.into_iter().collect(),
+            SlackMentions::All,
+        ));
+        // Carol pushed last to Dave's fork, the heading is still Dave's
+        ingredients.branches.push(WorkingBranch {
+            author: "Dave".into(),
+            email: "".into(),
+            name: "dave/PAY-13-pdf".into(),
+            last_activity: NaiveDate::from_ymd(2020, 5, 24).and_hms(14, 0, 0),
+            status: None,
+            contributors: vec![],
+            remote: Some("dave".into()),
+            local: false,
+            unpushed: None,
+        });
+        let report = ingredients.report();
+        let dave = report.authors.iter().find(|author| author.name == "Dave");
+        assert_eq!(dave.unwrap().mention, None);
+    }
+
+    #[test]
+    fn test_local_branch() {
+        let report = fixtures::ingredients().report();
+        let local = &report.tickets[1].branches[1];
+        assert!(local.local);
+        // never pushed, there's no page to link to
+        assert_eq!(local.url, None);
+    }
+
+    #[test]
+    fn test_stale_branches() {
+        let mut ingredients = fixtures::ingredients();
+        ingredients.mentions = mentions(SlackMentions::Stale);
+        let report = ingredients.report();
+        assert_eq!(report.stale_branches.len(), 1);
+        assert_eq!(report.stale_branches[0].author, "Alice");
+        assert_eq!(report.stale_branches[0].last_activity, "2020-05-01 15:30:00");
+        assert!(report.stale_branches[0].stale);
+        assert_eq!(report.authors[0].mention.as_deref(), Some("UALICE"));
+        assert!(report.authors[1].mention.is_none());
+        assert!(report.summary().stale_branches.len() == 1);
+        assert!(report.author_details()[0].stale_branches.is_empty());
+    }
+
+    #[test]
+    fn test_pull_request() {
+        let report = fixtures::ingredients().report();
+        let carol = &report.authors[2];
+        assert_eq!(carol.name, "Carol");
+        // the pull request's commits aren't listed on their own
+        assert_eq!(carol.sections.len(), 1);
+        assert_eq!(carol.sections[0].title, "Features");
+        let merge = &carol.sections[0].commits[0];
+        assert_eq!(merge.message, "feat: csv exports");
+        assert!(merge.merge);
+        // credited like the author, to whoever did the work rather than merged it
+        assert_eq!(merge.email, "carol@example.com");
+        let pull_request = merge.pull_request.as_ref().unwrap();
+        assert_eq!(
+            pull_request.url.as_deref(),
+            Some("https://github.com/team/pay/pull/12")
+        );
+        assert_eq!(pull_request.commits.len(), 2);
+        assert_eq!(carol.stats.unwrap().insertions, 10);
+    }
+
+    #[test]
+    fn test_releases() {
+        let report = fixtures::ingredients().report();
+        let release = &report.releases[0];
+        assert_eq!(
+            release.url.as_deref(),
+            Some("https://github.com/team/pay/tree/v1.4.0")
+        );
+        assert_eq!(release.commits[0].short_id, "bbbbbbb");
+        assert_eq!(report.summary().releases.len(), 1);
+        assert!(report.author_details()[0].releases.is_empty());
+    }
+}
@@ -0,0 +1,125 @@
+use super::{json::to_json, render, Report};
+use crate::DynErrResult;
+use handlebars::html_escape;
+use std::{error::Error, fmt::Display, str::FromStr};
+
+const MARKDOWN_TEMPLATE: &str = include_str!("../../templates/markdown.hbs");
+const HTML_TEMPLATE: &str = include_str!("../../templates/html.hbs");
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Default)]
+pub enum Format {
+    #[default]
+    Slack,
+    Markdown,
+    Html,
+    Json,
+}
+
+#[derive(Debug)]
+pub struct FormatError(String);
+
+impl Error for FormatError {}
+
+impl Display for FormatError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Unknown format {}, use slack, md, html or json", self.0)
+    }
+}
+
+impl FromStr for Format {
+    type Err = FormatError;
+
+    fn from_str(format: &str) -> Result<Self, Self::Err> {
+        match format {
+            "slack" => Ok(Format::Slack),
+            "md" | "markdown" => Ok(Format::Markdown),
+            "html" => Ok(Format::Html),
+            "json" => Ok(Format::Json),
+            _ => Err(FormatError(format.into())),
+        }
+    }
+}
+
+/// Renders the report for archiving or scripts, slack has its own renderer
+pub fn export(report: &Report, format: Format) -> DynErrResult<String> {
+    match format {
+        Format::Markdown => render(MARKDOWN_TEMPLATE, escape_markdown, report),
+        Format::Html => render(HTML_TEMPLATE, html_escape, report),
+        Format::Json => to_json(report),
+        Format::Slack => crate::slack::format_slack(report, None),
+    }
+}
+
+/// Backslash the characters which would turn commit messages into markup
+fn escape_markdown(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if "\\`*_[]<>#|".contains(c) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{export, Format};
+    use crate::report::fixtures;
+
+    #[test]
+    fn test_markdown_snapshot() {
+        let report = fixtures::ingredients().report();
+        assert_eq!(
+            export(&report, Format::Markdown).unwrap(),
+            include_str!("snapshots/report.md")
+        );
+    }
+
+    #[test]
+    fn test_html_snapshot() {
+        let report = fixtures::ingredients().report();
+        assert_eq!(
+            export(&report, Format::Html).unwrap(),
+            include_str!("snapshots/report.html")
+        );
+    }
+
+    #[test]
+    fn test_json_snapshot() {
+        let report = fixtures::ingredients().report();
+        assert_eq!(
+            export(&report, Format::Json).unwrap(),
+            include_str!("snapshots/report.json").trim_end()
+        );
+    }
+
+    #[test]
+    fn test_markdown_code_span() {
+        let mut report = fixtures::ingredients().report();
+        let merge = &mut report.authors[2].sections[0].commits[0];
+        merge.pull_request.as_mut().unwrap().source_branch = "fix_rounding".into();
+        // backslashes show literally in a code span
+        let markdown = export(&report, Format::Markdown).unwrap();
+        assert!(markdown.contains("from `fix_rounding`"));
+    }
+
+    #[test]
+    fn test_json_category_keys() {
+        let report = fixtures::ingredients().report();
+        let json: serde_json::Value =
+            serde_json::from_str(&export(&report, Format::Json).unwrap()).unwrap();
+        assert!(json.get("header").is_none());
+        assert!(json.get("categorized").is_none());
+        let alice = &json["authors"][0];
+        assert_eq!(alice["sections"][0]["category"], "feature");
+        assert_eq!(alice["sections"][0]["commits"][0]["category"], "feature");
+    }
+
+    #[test]
+    fn test_parse_format() {
+        assert_eq!("md".parse::<Format>().unwrap(), Format::Markdown);
+        assert_eq!("json".parse::<Format>().unwrap(), Format::Json);
+        assert!("pdf".parse::<Format>().is_err());
+    }
+}
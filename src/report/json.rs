@@ -0,0 +1,174 @@
+use super::{
+    Report, ReportAuthor, ReportBranch, ReportCommit, ReportPullRequest, ReportRelease,
+    ReportTicket,
+};
+use crate::{
+    git::{Category, DiffStats},
+    DynErrResult,
+};
+use serde::Serialize;
+
+/// Bumped whenever a field of the json report is renamed or removed
+const JSON_SCHEMA_VERSION: u32 = 2;
+
+/// The report for scripts, without what only templates need like `header` or `categorized`,
+/// and with categories named by key rather than by title
+#[derive(Serialize)]
+struct JsonReport<'a> {
+    version: u32,
+    scope: &'a Option<String>,
+    from: &'a str,
+    to: &'a str,
+    notes: &'a [String],
+    stats: Option<DiffStats>,
+    tickets: Vec<JsonTicket<'a>>,
+    releases: Vec<JsonRelease<'a>>,
+    stale_branches: &'a [ReportBranch],
+    authors: Vec<JsonAuthor<'a>>,
+}
+
+#[derive(Serialize)]
+struct JsonCommit<'a> {
+    id: &'a str,
+    short_id: &'a str,
+    url: &'a Option<String>,
+    author: &'a str,
+    email: &'a str,
+    message: &'a str,
+    body: &'a str,
+    authored_at: &'a str,
+    committed_at: &'a str,
+    merge: bool,
+    category: Category,
+    pull_request: Option<JsonPullRequest<'a>>,
+}
+
+#[derive(Serialize)]
+struct JsonPullRequest<'a> {
+    reference: &'a str,
+    url: &'a Option<String>,
+    source_branch: &'a str,
+    commits: Vec<JsonCommit<'a>>,
+}
+
+#[derive(Serialize)]
+struct JsonTicket<'a> {
+    key: &'a str,
+    url: &'a Option<String>,
+    commits: Vec<JsonCommit<'a>>,
+    branches: &'a [ReportBranch],
+}
+
+#[derive(Serialize)]
+struct JsonRelease<'a> {
+    tag: &'a str,
+    url: &'a Option<String>,
+    tagged_at: &'a str,
+    commits: Vec<JsonCommit<'a>>,
+}
+
+#[derive(Serialize)]
+struct JsonAuthor<'a> {
+    name: &'a str,
+    mention: &'a Option<String>,
+    stats: Option<DiffStats>,
+    sections: Vec<JsonSection<'a>>,
+    branches: &'a [ReportBranch],
+}
+
+#[derive(Serialize)]
+struct JsonSection<'a> {
+    category: Category,
+    commits: Vec<JsonCommit<'a>>,
+}
+
+pub fn to_json(report: &Report) -> DynErrResult<String> {
+    let json = JsonReport {
+        version: JSON_SCHEMA_VERSION,
+        scope: &report.scope,
+        from: &report.from,
+        to: &report.to,
+        notes: &report.notes,
+        stats: report.stats,
+        tickets: report.tickets.iter().map(JsonTicket::from).collect(),
+        releases: report.releases.iter().map(JsonRelease::from).collect(),
+        stale_branches: &report.stale_branches,
+        authors: report.authors.iter().map(JsonAuthor::from).collect(),
+    };
+    serde_json::to_string_pretty(&json).map_err(Box::from)
+}
+
+fn commits(commits: &[ReportCommit]) -> Vec<JsonCommit<'_>> {
+    commits.iter().map(JsonCommit::from).collect()
+}
+
+impl<'a> From<&'a ReportCommit> for JsonCommit<'a> {
+    fn from(commit: &'a ReportCommit) -> Self {
+        Self {
+            id: &commit.id,
+            short_id: &commit.short_id,
+            url: &commit.url,
+            author: &commit.author,
+            email: &commit.email,
+            message: &commit.message,
+            body: &commit.body,
+            authored_at: &commit.authored_at,
+            committed_at: &commit.committed_at,
+            merge: commit.merge,
+            category: commit.kind,
+            pull_request: commit.pull_request.as_ref().map(JsonPullRequest::from),
+        }
+    }
+}
+
+impl<'a> From<&'a ReportPullRequest> for JsonPullRequest<'a> {
+    fn from(pull_request: &'a ReportPullRequest) -> Self {
+        Self {
+            reference: &pull_request.reference,
+            url: &pull_request.url,
+            source_branch: &pull_request.source_branch,
+            commits: commits(&pull_request.commits),
+        }
+    }
+}
+
+impl<'a> From<&'a ReportTicket> for JsonTicket<'a> {
+    fn from(ticket: &'a ReportTicket) -> Self {
+        Self {
+            key: &ticket.key,
+            url: &ticket.url,
+            commits: commits(&ticket.commits),
+            branches: &ticket.branches,
+        }
+    }
+}
+
+impl<'a> From<&'a ReportRelease> for JsonRelease<'a> {
+    fn from(release: &'a ReportRelease) -> Self {
+        Self {
+            tag: &release.tag,
+            url: &release.url,
+            tagged_at: &release.tagged_at,
+            commits: commits(&release.commits),
+        }
+    }
+}
+
+impl<'a> From<&'a ReportAuthor> for JsonAuthor<'a> {
+    fn from(author: &'a ReportAuthor) -> Self {
+        Self {
+            name: &author.name,
+            mention: &author.mention,
+            stats: author.stats,
+            sections: author
+                .sections
+                .iter()
+                .map(|section| JsonSection {
+                    category: section.kind,
+                    commits: commits(&section.commits),
+                })
+                .collect(),
+            branches: &author.branches,
+        }
+    }
+}
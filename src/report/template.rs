@@ -0,0 +1,17 @@
+use super::Report;
+use crate::DynErrResult;
+use handlebars::{handlebars_helper, Handlebars};
+
+// `{{len commits}}`, templates often show how many items a list has
+handlebars_helper!(len: |items: array| items.len());
+
+/// Renders the report with a handlebars template, `escape` protects the output format
+/// from the characters it treats specially
+pub fn render(template: &str, escape: fn(&str) -> String, report: &Report) -> DynErrResult<String> {
+    let mut handlebars = Handlebars::new();
+    handlebars.register_escape_fn(escape);
+    handlebars.register_helper("len", Box::new(len));
+    handlebars
+        .render_template(template, report)
+        .map_err(Box::from)
+}
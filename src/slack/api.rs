@@ -0,0 +1,270 @@
+use super::SlackThread;
+use crate::{cli::log, config::Config, DynErrResult};
+use reqwest::blocking;
+use serde::{Deserialize, Serialize};
+use std::{error::Error, fmt::Display};
+
+pub const DEFAULT_API_URL: &str = "https://slack.com/api";
+
+#[derive(Debug)]
+pub enum SlackApiError {
+    Api(String),
+}
+
+impl Error for SlackApiError {}
+
+impl Display for SlackApiError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SlackApiError::Api(error) => write!(f, "Slack API refused the message: {}", error),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct PostMessage<'a> {
+    channel: &'a str,
+    text: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    thread_ts: Option<&'a str>,
+}
+
+#[derive(Serialize)]
+struct CompleteUpload<'a> {
+    files: [UploadedFile<'a>; 1],
+    channel_id: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    thread_ts: Option<&'a str>,
+}
+
+#[derive(Serialize)]
+struct UploadedFile<'a> {
+    id: &'a str,
+    title: &'a str,
+}
+
+#[derive(Deserialize)]
+struct ApiResponse {
+    ok: bool,
+    ts: Option<String>,
+    error: Option<String>,
+    /// Where `files.getUploadURLExternal` wants the file's content
+    upload_url: Option<String>,
+    file_id: Option<String>,
+}
+
+impl ApiResponse {
+    fn check(self) -> DynErrResult<ApiResponse> {
+        if self.ok {
+            Ok(self)
+        } else {
+            let error = self.error.unwrap_or_else(|| "unknown error".into());
+            Err(SlackApiError::Api(error).into())
+        }
+    }
+}
+
+/// Posts with a bot token, unlike the web hook it can reply in threads
+pub struct SlackApi {
+    pub base_url: String,
+    pub token: String,
+    pub channel: String,
+}
+
+impl SlackApi {
+    /// The api is used instead of the web hook once a bot token and a channel are configured
+    pub fn from_config(config: &Config) -> Option<SlackApi> {
+        match (&config.slack_bot_token, &config.slack_channel) {
+            (Some(token), Some(channel)) => Some(SlackApi {
+                base_url: config
+                    .slack_api_url
+                    .clone()
+                    .unwrap_or_else(|| DEFAULT_API_URL.into()),
+                token: token.clone(),
+                channel: channel.clone(),
+            }),
+            _ => None,
+        }
+    }
+
+    /// Posts the parent to the channel, then the replies and the attachment in its thread
+    pub fn post_thread(&self, thread: &SlackThread) -> DynErrResult<()> {
+        let ts = self.post_message(&thread.parent, None)?;
+        for reply in &thread.replies {
+            self.post_message(reply, Some(&ts))?;
+        }
+        if let Some(attachment) = &thread.attachment {
+            self.upload_file(attachment, Some(&ts))?;
+        }
+        Ok(())
+    }
+
+    /// Returns the `ts` of the new message, which identifies the thread
+    pub fn post_message(&self, text: &str, thread_ts: Option<&str>) -> DynErrResult<String> {
+        let message = PostMessage {
+            channel: &self.channel,
+            text,
+            thread_ts,
+        };
+        log::message(format!("Posting to slack channel {}", self.channel));
+        let response: ApiResponse = blocking::Client::new()
+            .post(&self.method_url("chat.postMessage"))
+            .bearer_auth(&self.token)
+            .json(&message)
+            .send()?
+            .json()?;
+        match response.check()?.ts {
+            Some(ts) => Ok(ts),
+            None => Err(SlackApiError::Api("no ts in the response".into()).into()),
+        }
+    }
+
+    /// Uploads `content` as a markdown file in the channel, or in a thread. Slack wants
+    /// an upload url first, then the content, then the upload completed in the channel,
+    /// which has to be a channel id like `C123` for this
+    pub fn upload_file(&self, content: &str, thread_ts: Option<&str>) -> DynErrResult<()> {
+        log::message(format!(
+            "Uploading the report to slack channel {}",
+            self.channel
+        ));
+        let client = blocking::Client::new();
+        let length = content.len().to_string();
+        let form = [("filename", "git-retro.md"), ("length", length.as_str())];
+        let response = client
+            .post(&self.method_url("files.getUploadURLExternal"))
+            .bearer_auth(&self.token)
+            .form(&form)
+            .send()?
+            .json::<ApiResponse>()?
+            .check()?;
+        let (upload_url, file_id) = match (response.upload_url, response.file_id) {
+            (Some(upload_url), Some(file_id)) => (upload_url, file_id),
+            _ => return Err(SlackApiError::Api("no upload url in the response".into()).into()),
+        };
+        client
+            .post(&upload_url)
+            .body(content.to_string())
+            .send()?
+            .error_for_status()?;
+        let complete = CompleteUpload {
+            files: [UploadedFile {
+                id: &file_id,
+                title: "git-retro.md",
+            }],
+            channel_id: &self.channel,
+            thread_ts,
+        };
+        let response: ApiResponse = client
+            .post(&self.method_url("files.completeUploadExternal"))
+            .bearer_auth(&self.token)
+            .json(&complete)
+            .send()?
+            .json()?;
+        response.check().map(|_| ())
+    }
+
+    fn method_url(&self, method: &str) -> String {
+        format!("{}/{}", self.base_url.trim_end_matches('/'), method)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SlackApi, SlackThread};
+    use mockito::{mock, Matcher};
+    use serde_json::json;
+
+    fn api() -> SlackApi {
+        SlackApi {
+            base_url: mockito::server_url(),
+            token: "xoxb-test".into(),
+            channel: "C123".into(),
+        }
+    }
+
+    #[test]
+    fn test_post_thread() {
+        let parent = mock("POST", "/chat.postMessage")
+            .match_header("authorization", "Bearer xoxb-test")
+            .match_body(Matcher::Json(json!({"channel": "C123", "text": "summary"})))
+            .with_body(r#"{"ok": true, "ts": "1590357600.000100"}"#)
+            .create();
+        let replies = mock("POST", "/chat.postMessage")
+            .match_body(Matcher::PartialJson(
+                json!({"channel": "C123", "thread_ts": "1590357600.000100"}),
+            ))
+            .with_body(r#"{"ok": true, "ts": "1590357601.000200"}"#)
+            .expect(2)
+            .create();
+
+        let thread = SlackThread {
+            parent: "summary".into(),
+            replies: vec!["alice".into(), "bob".into()],
+            attachment: None,
+        };
+        api().post_thread(&thread).unwrap();
+        parent.assert();
+        replies.assert();
+    }
+
+    #[test]
+    fn test_post_collapsed() {
+        let parent = mock("POST", "/chat.postMessage")
+            .match_body(Matcher::PartialJson(json!({"text": "collapsed summary"})))
+            .with_body(r#"{"ok": true, "ts": "1590357700.000100"}"#)
+            .create();
+        let upload_url = mock("POST", "/files.getUploadURLExternal")
+            .match_header("authorization", "Bearer xoxb-test")
+            .match_body(Matcher::AllOf(vec![
+                Matcher::UrlEncoded("filename".into(), "git-retro.md".into()),
+                Matcher::UrlEncoded("length".into(), "14".into()),
+            ]))
+            .with_body(
+                json!({
+                    "ok": true,
+                    "upload_url": format!("{}/upload/F123", mockito::server_url()),
+                    "file_id": "F123",
+                })
+                .to_string(),
+            )
+            .create();
+        let content = mock("POST", "/upload/F123")
+            .match_body("# whole report")
+            .with_body("OK - 14")
+            .create();
+        let complete = mock("POST", "/files.completeUploadExternal")
+            .match_header("authorization", "Bearer xoxb-test")
+            .match_body(Matcher::Json(json!({
+                "files": [{"id": "F123", "title": "git-retro.md"}],
+                "channel_id": "C123",
+                "thread_ts": "1590357700.000100",
+            })))
+            .with_body(r#"{"ok": true, "files": []}"#)
+            .create();
+
+        let thread = SlackThread {
+            parent: "collapsed summary".into(),
+            replies: vec![],
+            attachment: Some("# whole report".into()),
+        };
+        api().post_thread(&thread).unwrap();
+        parent.assert();
+        upload_url.assert();
+        content.assert();
+        complete.assert();
+    }
+
+    #[test]
+    fn test_api_error() {
+        let _refused = mock("POST", "/chat.postMessage")
+            .match_body(Matcher::PartialJson(json!({"text": "refused"})))
+            .with_body(r#"{"ok": false, "error": "channel_not_found"}"#)
+            .create();
+
+        let error = api().post_message("refused", None).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Slack API refused the message: channel_not_found"
+        );
+    }
+}
@@ -0,0 +1,88 @@
+use crate::config::{Config, SlackMentions};
+use std::collections::BTreeMap;
+
+/// Maps git authors to slack users, so the heading of their section notifies them
+pub struct Mentions {
+    users: BTreeMap<String, String>,
+    only_stale: bool,
+}
+
+impl Mentions {
+    pub fn new(users: BTreeMap<String, String>, mode: SlackMentions) -> Self {
+        Self {
+            users,
+            only_stale: mode == SlackMentions::Stale,
+        }
+    }
+
+    /// Nobody is mentioned until `slack_users` has entries
+    pub fn from_config(config: &Config) -> Option<Self> {
+        if config.slack_users.is_empty() {
+            None
+        } else {
+            Some(Mentions::new(
+                config.slack_users.clone(),
+                config.slack_mentions,
+            ))
+        }
+    }
+
+    /// Slack user id of the author, `has_stale` tells if they left a branch behind
+    pub fn user_id<'a>(
+        &self,
+        name: &str,
+        emails: impl IntoIterator<Item = &'a String>,
+        has_stale: bool,
+    ) -> Option<String> {
+        if self.only_stale && !has_stale {
+            return None;
+        }
+        // emails first, two people can share a name
+        emails
+            .into_iter()
+            .find_map(|email| self.find(email))
+            .or_else(|| self.find(name))
+    }
+
+    fn find(&self, key: &str) -> Option<String> {
+        self.users
+            .iter()
+            .find(|(user, _)| user.eq_ignore_ascii_case(key))
+            .map(|(_, id)| id.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Mentions;
+    use crate::config::SlackMentions;
+
+    fn mentions(mode: SlackMentions) -> Mentions {
+        let users = vec![
+            ("alice@example.com".to_string(), "UALICE".to_string()),
+            ("Bob".to_string(), "UBOB".to_string()),
+        ];
+        Mentions::new(users.into_iter().collect(), mode)
+    }
+
+    #[test]
+    fn test_user_id() {
+        let mentions = mentions(SlackMentions::All);
+        let nobody: Vec<String> = vec![];
+        let alice = vec!["Alice@Example.com".to_string()];
+        assert_eq!(
+            mentions.user_id("Alice", &alice, false),
+            Some("UALICE".into())
+        );
+        assert_eq!(mentions.user_id("Bob", &nobody, false), Some("UBOB".into()));
+        assert_eq!(mentions.user_id("Carol", &nobody, false), None);
+    }
+
+    #[test]
+    fn test_only_stale() {
+        let mentions = mentions(SlackMentions::Stale);
+        let nobody: Vec<String> = vec![];
+        assert_eq!(mentions.user_id("Bob", &nobody, false), None);
+        assert_eq!(mentions.user_id("Bob", &nobody, true), Some("UBOB".into()));
+    }
+}
@@ -1,7 +1,13 @@
 use reqwest::blocking;
 
-use crate::{DynErrResult, cli::log, git::{search_interval::SearchInterval, RetroCommit, WorkingBranch}};
-use std::collections::BTreeMap;
+pub mod api;
+mod mentions;
+mod split;
+
+pub use mentions::Mentions;
+pub use split::{length, MAX_MESSAGE_LENGTH};
+
+use crate::{DynErrResult, cli::log, report::{export, render, Format, Report}};
 use serde::{Deserialize, Serialize};
 use serde_json;
 
@@ -10,55 +16,190 @@ struct Message {
     text: String
 }
 
-pub struct MessageIngredients<C, B>
-where
-    C: AsRef<[RetroCommit]>,
-    B: AsRef<[WorkingBranch]>,
-{
-    pub commits: C,
-    pub branches: B,
-    pub interval: SearchInterval,
+/// A report split for the slack api, the summary is posted first and
+/// the details of every author are replies in its thread
+#[derive(Serialize, Deserialize, Clone)]
+pub struct SlackThread {
+    pub parent: String,
+    pub replies: Vec<String>,
+    /// The whole report uploaded as a file in the thread, instead of the replies
+    #[serde(default)]
+    pub attachment: Option<String>,
+}
+
+pub const DEFAULT_TEMPLATE: &str = include_str!("../../templates/slack.hbs");
+
+/// Renders the report with the user's template, or with the default layout
+pub fn format_slack(report: &Report, template: Option<&str>) -> DynErrResult<String> {
+    render(template.unwrap_or(DEFAULT_TEMPLATE), escape, report)
+}
+
+/// Renders the report in as few messages as slack accepts, cutting it between authors
+pub fn format_slack_parts(
+    report: &Report,
+    template: Option<&str>,
+    max_length: usize,
+) -> DynErrResult<Vec<String>> {
+    let message = format_slack(report, template)?;
+    if split::length(&message) <= max_length {
+        return Ok(vec![message]);
+    }
+    let mut pieces = vec![format_slack(&report.summary(), template)?];
+    for details in report.author_details() {
+        pieces.push(format_slack(&details, template)?);
+    }
+    Ok(split::pack(pieces, max_length))
+}
+
+pub fn format_slack_thread(
+    report: &Report,
+    template: Option<&str>,
+    max_length: usize,
+) -> DynErrResult<SlackThread> {
+    let mut parent = split::split_lines(format_slack(&report.summary(), template)?, max_length);
+    // whatever doesn't fit in the parent becomes the first replies
+    let mut replies = parent.split_off(1);
+    for details in report.author_details() {
+        replies.extend(split::split_lines(format_slack(&details, template)?, max_length));
+    }
+    Ok(SlackThread {
+        parent: parent.pop().unwrap_or_default(),
+        replies,
+        attachment: None,
+    })
+}
+
+/// Only the summary is posted, the whole report is attached to it as a markdown file
+pub fn format_slack_collapsed(
+    report: &Report,
+    template: Option<&str>,
+) -> DynErrResult<SlackThread> {
+    let mut parent = format_slack(&report.summary(), template)?;
+    parent.push_str("_The details are in the attached file_\n");
+    Ok(SlackThread {
+        parent,
+        replies: vec![],
+        attachment: Some(export(report, Format::Markdown)?),
+    })
 }
 
-impl<C, B> MessageIngredients<C, B>
-where
-    C: AsRef<[RetroCommit]>,
-    B: AsRef<[WorkingBranch]>,
-{
-    pub fn format_slack(&self) -> String {
-        let mut author_commit_map: BTreeMap<String, Vec<String>> = BTreeMap::new();
-        for commit in self.commits.as_ref() {
-            let commits = author_commit_map.entry(commit.author.clone()).or_default();
-            commits.push(format!("[done] {}", commit.message));
-        }
-        for branch in self.branches.as_ref() {
-            let commits = author_commit_map.entry(branch.author.clone()).or_default();
-            commits.push(format!("[in-progress] {}", branch.name));
-        }
-
-        let mut message = format!("Team git-status from {} to {}\n", self.interval.from, self.interval.to);
-        for (author, jobs) in author_commit_map {
-            message.push_str(&format!("_{}_\n", author));
-            message.push_str("```\n");
-            for job in jobs {
-                message.push_str(&format!("    {}\n", job));
-            }
-            message.push_str("```\n");
-        }
-        message
+/// Slack treats `&`, `<` and `>` as control characters in message text
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+pub fn send_to_slack<T: AsRef<str>>(hook: T, log: T) -> DynErrResult<blocking::Response> {
+    let msg = Message {
+        text: log.as_ref().to_string()
+    };
+    let payload = serde_json::ser::to_string(&msg)?;
+    log::message(format!("Sending to slack \n{}", &payload));
+    let client = blocking::Client::new();
+    client
+        .post(hook.as_ref())
+        .body(payload)
+        .send()
+        .map_err(Box::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::report::fixtures;
+
+    #[test]
+    fn test_default_template() {
+        let report = fixtures::ingredients().report();
+        let message = super::format_slack(&report, None).unwrap();
+        assert_eq!(
+            message,
+            r"Team git-status from 2020-05-23 22:00:00 to 2020-05-24 22:00:00
+Repository: +40 -8 in 4 files
+*Tickets*
+  <https://jira.example.com/browse/PAY-7|PAY-7>
+    • [done] <https://github.com/team/pay/commit/aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa|aaaaaaa> feat(api): PAY-7 refunds (Alice)
+    • [done] <https://github.com/team/pay/commit/bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb|bbbbbbb> fix: PAY-7 rounding &lt;cents&gt; (Bob)
+  <https://jira.example.com/browse/PAY-9|PAY-9>
+    • [in-progress] <https://github.com/team/pay/tree/PAY-9-payouts|origin/PAY-9-payouts> [2 ahead, 1 behind, conflicts] (Bob ×3, Alice ×1)
+    • [local, never pushed] PAY-9-fees [1 ahead, 0 behind] (Bob)
+*Releases*
+  <https://github.com/team/pay/tree/v1.4.0|v1.4.0> (2020-05-24 13:00:00)
+    • [done] <https://github.com/team/pay/commit/bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb|bbbbbbb> fix: PAY-7 rounding &lt;cents&gt; (Bob)
+    • [done] <https://github.com/team/pay/commit/aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa|aaaaaaa> feat(api): PAY-7 refunds (Alice)
+*Stale branches*
+  • <https://github.com/team/pay/tree/old-experiment|origin/old-experiment> (Alice, last commit 2020-05-01 15:30:00)
+_Alice_ (+20 -4 in 2 files)
+  *Features*
+    • [done] <https://github.com/team/pay/commit/aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa|aaaaaaa> feat(api): PAY-7 refunds
+  *Chores*
+    • [done] <https://github.com/team/pay/commit/cccccccccccccccccccccccccccccccccccccccc|ccccccc> chore: bump deps &amp; tidy
+  *In progress*
+    • [in-progress] <https://github.com/team/pay/tree/PAY-9-payouts|origin/PAY-9-payouts> [2 ahead, 1 behind, conflicts] (Bob ×3, Alice ×1)
+_Bob_ (+10 -2 in 1 files)
+  *Fixes*
+    • [done] <https://github.com/team/pay/commit/bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb|bbbbbbb> fix: PAY-7 rounding &lt;cents&gt;
+  *In progress*
+    • [in-progress] <https://github.com/team/pay/tree/PAY-9-payouts|origin/PAY-9-payouts> [2 ahead, 1 behind, conflicts] (Bob ×3, Alice ×1)
+    • [local, never pushed] PAY-9-fees [1 ahead, 0 behind]
+_Carol_ (+10 -2 in 1 files)
+  *Features*
+    • [done] <https://github.com/team/pay/commit/dddddddddddddddddddddddddddddddddddddddd|ddddddd> feat: csv exports (<https://github.com/team/pay/pull/12|#12> from csv-exports, 2 commits)
+"
+        );
+    }
+
+    #[test]
+    fn test_thread_split() {
+        let report = fixtures::ingredients().report();
+        let thread = super::format_slack_thread(&report, None, super::MAX_MESSAGE_LENGTH).unwrap();
+        assert!(thread.parent.starts_with("Team git-status"));
+        assert!(thread.parent.contains("*Tickets*"));
+        assert!(!thread.parent.contains("_Alice_"));
+        assert_eq!(thread.replies.len(), 3);
+        assert!(thread.replies[0].starts_with("_Alice_ (+20 -4 in 2 files)\n"));
+        assert!(thread.replies[1].starts_with("_Bob_"));
+        assert!(thread.replies[2].starts_with("_Carol_"));
+    }
+
+    #[test]
+    fn test_parts_split_between_authors() {
+        let report = fixtures::ingredients().report();
+        // just enough room for the summary, the authors don't fit next to it
+        let summary = super::format_slack(&report.summary(), None).unwrap();
+        let max_length = super::length(&summary);
+        let parts = super::format_slack_parts(&report, None, max_length).unwrap();
+        assert_eq!(parts[0], summary);
+        assert!(parts[1].starts_with("_Alice_"));
+        assert!(parts[1..].iter().all(|part| part.starts_with('_')));
+        assert!(parts.iter().all(|part| super::length(part) <= max_length));
+        assert_eq!(parts.concat(), super::format_slack(&report, None).unwrap());
+    }
+
+    #[test]
+    fn test_mention_heading() {
+        let mut report = fixtures::ingredients().report();
+        report.authors[1].mention = Some("UBOB".into());
+        let message = super::format_slack(&report, None).unwrap();
+        assert!(message.contains("_Alice_ (+20 -4 in 2 files)\n"));
+        assert!(message.contains("\n<@UBOB> (+10 -2 in 1 files)\n"));
+    }
+
+    #[test]
+    fn test_scope_header() {
+        let mut report = fixtures::ingredients().report();
+        report.scope = Some("payments".into());
+        let message = super::format_slack(&report, None).unwrap();
+        assert!(message.starts_with("Team git-status of payments from 2020-05-23 22:00:00"));
     }
 
-    pub fn send_to_slack<T: AsRef<str>>(&self, hook: T, log: T) -> DynErrResult<blocking::Response> {
-        let msg = Message {
-            text: log.as_ref().to_string()
-        };
-        let payload = serde_json::ser::to_string(&msg)?;
-        log::message(format!("Sending to slack \n{}", &payload));
-        let client = blocking::Client::new();
-        client
-            .post(hook.as_ref())
-            .body(payload)
-            .send()
-            .map_err(Box::from)
+    #[test]
+    fn test_notes_under_header() {
+        let mut report = fixtures::ingredients().report();
+        report.notes = vec!["Fetching the remote failed, the data may be stale".into()];
+        let message = super::format_slack(&report, None).unwrap();
+        assert!(message.contains(
+            "22:00:00\n_Fetching the remote failed, the data may be stale_\nRepository:"
+        ));
     }
 }
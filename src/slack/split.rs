@@ -0,0 +1,90 @@
+use std::mem;
+
+/// Slack advises to keep the text of a message under 4000 characters, longer ones get truncated
+pub const MAX_MESSAGE_LENGTH: usize = 4000;
+
+/// Slack counts characters, not bytes
+pub fn length(text: &str) -> usize {
+    text.chars().count()
+}
+
+/// Joins consecutive pieces, like the blocks of every author, in as few messages
+/// as possible without cutting a piece unless it's too long on its own
+pub fn pack(pieces: Vec<String>, max_length: usize) -> Vec<String> {
+    let mut messages = vec![];
+    let mut current = String::new();
+    for piece in pieces
+        .into_iter()
+        .flat_map(|piece| split_lines(piece, max_length))
+    {
+        if !current.is_empty() && length(&current) + length(&piece) > max_length {
+            messages.push(mem::take(&mut current));
+        }
+        current.push_str(&piece);
+    }
+    if !current.is_empty() {
+        messages.push(current);
+    }
+    messages
+}
+
+/// Cuts a text which is too long between lines, or inside a line when even that is too long
+pub fn split_lines(text: String, max_length: usize) -> Vec<String> {
+    if length(&text) <= max_length {
+        return vec![text];
+    }
+    let mut chunks = vec![];
+    let mut current = String::new();
+    for line in text.split_inclusive('\n') {
+        if !current.is_empty() && length(&current) + length(line) > max_length {
+            chunks.push(mem::take(&mut current));
+        }
+        if length(line) > max_length {
+            let chars: Vec<char> = line.chars().collect();
+            chunks.extend(chars.chunks(max_length).map(|chunk| chunk.iter().collect()));
+        } else {
+            current.push_str(line);
+        }
+    }
+    if !current.is_empty() {
+        chunks.push(current);
+    }
+    chunks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{pack, split_lines};
+
+    #[test]
+    fn test_pack_on_boundaries() {
+        let pieces = vec![
+            "header\n".to_string(),
+            "alice\n".to_string(),
+            "bob\n".to_string(),
+            "carol\n".to_string(),
+        ];
+        assert_eq!(
+            pack(pieces, 14),
+            vec!["header\nalice\n".to_string(), "bob\ncarol\n".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_pack_fits() {
+        let pieces = vec!["header\n".to_string(), "alice\n".to_string()];
+        assert_eq!(pack(pieces, 4000), vec!["header\nalice\n".to_string()]);
+    }
+
+    #[test]
+    fn test_split_long_piece() {
+        assert_eq!(
+            split_lines("one\ntwo\nthree\n".into(), 8),
+            vec!["one\ntwo\n".to_string(), "three\n".to_string()]
+        );
+        assert_eq!(
+            split_lines("abcdefgh\nij".into(), 4),
+            vec!["abcd", "efgh", "\n", "ij"]
+        );
+    }
+}
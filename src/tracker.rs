@@ -0,0 +1,101 @@
+use crate::{config::Config, DynErrResult};
+use regex::Regex;
+
+/// Jira style keys like `PAY-123`, the project key starts with two letters
+const DEFAULT_ISSUE_PATTERN: &str = r"\b[A-Z]{2}[A-Z0-9]*-[0-9]+\b";
+/// Look like keys to the default pattern but name encodings, hashes and standards
+const TECHNICAL_KEYS: [&str; 8] = ["UTF", "UCS", "SHA", "MD", "ISO", "RFC", "CVE", "AES"];
+const ISSUE: &str = "{issue}";
+
+/// Finds issue keys in commit messages and branch names and links them to the tracker
+pub struct IssueTracker {
+    pattern: Regex,
+    url_template: Option<String>,
+    /// Only with the default pattern, a configured one is taken as it is
+    skip_technical: bool,
+}
+
+impl IssueTracker {
+    pub fn new(pattern: &str, url_template: Option<String>) -> DynErrResult<Self> {
+        Ok(Self {
+            pattern: Regex::new(pattern)?,
+            url_template,
+            skip_technical: pattern == DEFAULT_ISSUE_PATTERN,
+        })
+    }
+
+    /// Tracking is off until either the pattern or the url template is configured
+    pub fn from_config(config: &Config) -> DynErrResult<Option<Self>> {
+        match (&config.issue_pattern, &config.issue_url_template) {
+            (None, None) => Ok(None),
+            (pattern, url_template) => IssueTracker::new(
+                pattern.as_deref().unwrap_or(DEFAULT_ISSUE_PATTERN),
+                url_template.clone(),
+            )
+            .map(Some),
+        }
+    }
+
+    /// Every distinct key in `text`, in the order they appear
+    pub fn keys(&self, text: &str) -> Vec<String> {
+        let mut keys: Vec<String> = vec![];
+        for key in self.pattern.find_iter(text) {
+            if self.skip_technical && is_technical(key.as_str()) {
+                continue;
+            }
+            if !keys.iter().any(|known| known == key.as_str()) {
+                keys.push(key.as_str().into());
+            }
+        }
+        keys
+    }
+
+    pub fn url(&self, key: &str) -> Option<String> {
+        self.url_template
+            .as_ref()
+            .map(|template| template.replace(ISSUE, key))
+    }
+}
+
+/// `UTF-8`, `SHA-256` or `ISO-8601`
+fn is_technical(key: &str) -> bool {
+    let project = key.split('-').next().unwrap_or_default();
+    TECHNICAL_KEYS.contains(&project)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{IssueTracker, DEFAULT_ISSUE_PATTERN};
+
+    #[test]
+    fn test_default_pattern() {
+        let tracker = IssueTracker::new(DEFAULT_ISSUE_PATTERN, None).unwrap();
+        assert_eq!(
+            tracker.keys("PAY-123 fix rounding, see PAY-123 and OPS-7"),
+            vec!["PAY-123".to_string(), "OPS-7".to_string()]
+        );
+        assert_eq!(
+            tracker.keys("origin/PAY-42-refunds"),
+            vec!["PAY-42".to_string()]
+        );
+        assert!(tracker.keys("no ticket here").is_empty());
+        assert!(tracker
+            .keys("read UTF-8, check SHA-256 and ISO-8601 dates, X-1")
+            .is_empty());
+        assert_eq!(tracker.url("PAY-1"), None);
+    }
+
+    #[test]
+    fn test_custom_pattern_and_url() {
+        let tracker = IssueTracker::new(
+            r"\bpay-\d+\b",
+            Some("https://tracker.example.com/browse/{issue}".into()),
+        )
+        .unwrap();
+        assert_eq!(tracker.keys("closes pay-12"), vec!["pay-12".to_string()]);
+        assert_eq!(
+            tracker.url("pay-12"),
+            Some("https://tracker.example.com/browse/pay-12".into())
+        );
+    }
+}